@@ -0,0 +1,132 @@
+//! Summing long streams of lengths without overflow or drift.
+use crate::{Microns, Microns64, RoundingMode};
+use core::ops::AddAssign;
+
+/// An exact running total of Microns, for streams too long for any fixed
+/// width to hold, such as the filament fed over a multi-day print.
+///
+/// The total is an `i128` count of micrometers, which no realistic stream
+/// can overflow.
+///
+/// ```
+/// use microns::{Microns, MicronsAccumulator};
+///
+/// let mut fed = MicronsAccumulator::new();
+/// for _ in 0..3_000_000 {
+///     fed += Microns(1_500);
+/// }
+/// assert_eq!(fed.total_um(), 4_500_000_000);
+/// assert_eq!(fed.mean(), Some(Microns(1_500)));
+/// ```
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MicronsAccumulator {
+    total: i128,
+    count: u64,
+}
+
+impl MicronsAccumulator {
+    pub const fn new() -> Self {
+        MicronsAccumulator { total: 0, count: 0 }
+    }
+
+    pub fn add(&mut self, m: Microns) {
+        self.total += m.0 as i128;
+        self.count += 1;
+    }
+
+    /// Folds in another accumulator, e.g. one per worker thread.
+    pub fn merge(&mut self, other: &MicronsAccumulator) {
+        self.total += other.total;
+        self.count += other.count;
+    }
+
+    /// The number of values added.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The exact total in micrometers.
+    pub const fn total_um(&self) -> i128 {
+        self.total
+    }
+
+    /// The total in millimeters, rounded only by the conversion to `f64`.
+    pub fn total_mm(&self) -> f64 {
+        self.total as f64 / 1000.0
+    }
+
+    /// The total as a Microns64, or `None` if it doesn't fit.
+    pub fn total(&self) -> Option<Microns64> {
+        i64::try_from(self.total).ok().map(Microns64::new)
+    }
+
+    /// The mean, rounded to the nearest micrometer with ties away from
+    /// zero, or `None` if nothing has been added.
+    pub fn mean(&self) -> Option<Microns> {
+        if self.count == 0 {
+            return None;
+        }
+        let mean = RoundingMode::HalfUp.div_i128(self.total, self.count as i128);
+        // the mean of Microns values is always in range
+        Some(Microns(mean as i32))
+    }
+}
+
+impl AddAssign<Microns> for MicronsAccumulator {
+    fn add_assign(&mut self, m: Microns) {
+        self.add(m);
+    }
+}
+
+impl Extend<Microns> for MicronsAccumulator {
+    fn extend<I: IntoIterator<Item = Microns>>(&mut self, iter: I) {
+        for m in iter {
+            self.add(m);
+        }
+    }
+}
+
+impl<'a> Extend<&'a Microns> for MicronsAccumulator {
+    fn extend<I: IntoIterator<Item = &'a Microns>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl FromIterator<Microns> for MicronsAccumulator {
+    fn from_iter<I: IntoIterator<Item = Microns>>(iter: I) -> Self {
+        let mut acc = MicronsAccumulator::new();
+        acc.extend(iter);
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulate() {
+        let empty = MicronsAccumulator::new();
+        assert_eq!((empty.count(), empty.mean()), (0, None));
+        assert_eq!(empty.total(), Some(Microns64::ZERO));
+
+        let mut acc: MicronsAccumulator = [Microns::MAX; 4].into_iter().collect();
+        assert_eq!(acc.total_um(), 4 * i32::MAX as i128);
+        assert_eq!(acc.mean(), Some(Microns::MAX));
+        let shard: MicronsAccumulator = [Microns(-3), Microns(-4)].into_iter().collect();
+        acc.merge(&shard);
+        acc.extend(&[Microns(1)]);
+        assert_eq!(acc.count(), 7);
+        assert_eq!(acc.total_um(), 4 * i32::MAX as i128 - 6);
+        assert_eq!(acc.total_mm(), 8_589_934.582);
+
+        let mut tiny = MicronsAccumulator::new();
+        tiny.extend([Microns(-1), Microns(-2)]);
+        assert_eq!(tiny.mean(), Some(Microns(-2)));
+        let huge = MicronsAccumulator {
+            total: i64::MAX as i128 + 1,
+            count: 1 << 40,
+        };
+        assert_eq!(huge.total(), None);
+    }
+}
@@ -0,0 +1,135 @@
+//! `approx` trait impls, behind the `approx` feature.
+//!
+//! Tolerances are Microns: `assert_abs_diff_eq!(a, b, epsilon = Microns(5))`.
+//! Defaults are all zero, so the default comparisons are exact. For
+//! `relative_eq`, `max_relative` is read as micrometers per millimeter of the
+//! larger magnitude, so `Microns(1)` allows 0.1%. One ulp is one micrometer.
+//! Points and vectors compare component-wise with the same tolerances.
+use crate::{Microns, Point2, Point3, Vec2, Vec3};
+use ::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+
+impl AbsDiffEq for Microns {
+    type Epsilon = Microns;
+
+    fn default_epsilon() -> Microns {
+        Microns::ZERO
+    }
+
+    fn abs_diff_eq(&self, other: &Microns, epsilon: Microns) -> bool {
+        self.approx_eq(*other, epsilon)
+    }
+}
+
+impl RelativeEq for Microns {
+    fn default_max_relative() -> Microns {
+        Microns::ZERO
+    }
+
+    fn relative_eq(&self, other: &Microns, epsilon: Microns, max_relative: Microns) -> bool {
+        if self.approx_eq(*other, epsilon) {
+            return true;
+        }
+        let largest = self.0.unsigned_abs().max(other.0.unsigned_abs()) as u64;
+        let allowed = largest * max_relative.0.unsigned_abs() as u64 / 1000;
+        self.0.abs_diff(other.0) as u64 <= allowed
+    }
+}
+
+impl UlpsEq for Microns {
+    fn default_max_ulps() -> u32 {
+        0
+    }
+
+    fn ulps_eq(&self, other: &Microns, epsilon: Microns, max_ulps: u32) -> bool {
+        self.approx_eq(*other, epsilon) || self.0.abs_diff(other.0) <= max_ulps
+    }
+}
+
+macro_rules! impl_approx {
+    ($($name:ident { $($c:ident),* }),*) => {$(
+        impl AbsDiffEq for $name {
+            type Epsilon = Microns;
+
+            fn default_epsilon() -> Microns {
+                Microns::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &$name, epsilon: Microns) -> bool {
+                $(self.$c.abs_diff_eq(&other.$c, epsilon))&&*
+            }
+        }
+
+        impl RelativeEq for $name {
+            fn default_max_relative() -> Microns {
+                Microns::default_max_relative()
+            }
+
+            fn relative_eq(&self, other: &$name, epsilon: Microns, max_relative: Microns) -> bool {
+                $(self.$c.relative_eq(&other.$c, epsilon, max_relative))&&*
+            }
+        }
+
+        impl UlpsEq for $name {
+            fn default_max_ulps() -> u32 {
+                Microns::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &$name, epsilon: Microns, max_ulps: u32) -> bool {
+                $(self.$c.ulps_eq(&other.$c, epsilon, max_ulps))&&*
+            }
+        }
+    )*};
+}
+impl_approx!(
+    Point2 { x, y },
+    Point3 { x, y, z },
+    Vec2 { x, y },
+    Vec3 { x, y, z }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    #[test]
+    fn test_approx() {
+        assert_abs_diff_eq!(Microns(1000), Microns(1000));
+        assert_abs_diff_eq!(Microns(1000), Microns(1004), epsilon = Microns(5));
+        assert!(Microns(1000).abs_diff_ne(&Microns(1001), Microns::ZERO));
+        assert_relative_eq!(Microns(10_000), Microns(10_010), max_relative = Microns(1));
+        assert_relative_ne!(Microns(10_000), Microns(10_011), max_relative = Microns(1));
+        assert_relative_eq!(Microns::MIN, Microns::MAX, max_relative = Microns(2000));
+        assert_ulps_eq!(Microns(-3), Microns(0), max_ulps = 3);
+    }
+
+    #[test]
+    fn test_approx_geom() {
+        let p = Point3::new(Microns(1000), Microns(-20), Microns(5));
+        let q = Point3::new(Microns(1004), Microns(-24), Microns(5));
+        assert_abs_diff_eq!(p, p);
+        assert_abs_diff_eq!(p, q, epsilon = Microns(4));
+        assert!(p.abs_diff_ne(&q, Microns(3)));
+        let v = Vec2::new(Microns(10_000), Microns(0));
+        assert_relative_eq!(
+            v,
+            Vec2::new(Microns(10_010), Microns(0)),
+            max_relative = Microns(1)
+        );
+        assert_relative_ne!(
+            v,
+            Vec2::new(Microns(10_000), Microns(1)),
+            max_relative = Microns(1)
+        );
+        assert_ulps_eq!(
+            Point2::default(),
+            Point2::new(Microns(2), Microns(-2)),
+            max_ulps = 2
+        );
+        assert_abs_diff_eq!(
+            Vec3::default(),
+            Vec3::new(Microns(1), Microns(1), Microns(1)),
+            epsilon = Microns(1)
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Area in square microns, the result of multiplying two lengths.
+use crate::Microns;
+use core::iter::Sum;
+use core::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// SquareMicrons holds an area in µm², wide enough for the product of
+/// any two Microns.
+pub struct SquareMicrons(pub i64);
+
+impl SquareMicrons {
+    pub const ZERO: SquareMicrons = SquareMicrons(0);
+
+    pub fn abs(&self) -> Self {
+        SquareMicrons(self.0.abs())
+    }
+
+    /// The area in square millimeters.
+    pub fn as_mm2(self) -> f64 {
+        self.0 as f64 / 1_000_000.0
+    }
+
+    /// The side length of a square with this area, rounded down.
+    /// Panics if the area is negative or the root doesn't fit in Microns.
+    pub fn isqrt(self) -> Microns {
+        assert!(self.0 >= 0, "isqrt of a negative area");
+        self.checked_isqrt().expect("Value out of range")
+    }
+
+    /// Returns `None` if the area is negative or the root doesn't fit in Microns.
+    pub fn checked_isqrt(self) -> Option<Microns> {
+        let n = u128::try_from(self.0).ok()?;
+        i32::try_from(isqrt(n)).ok().map(Microns)
+    }
+}
+
+/// Floor of the square root, by Newton's method from an initial guess above the root.
+pub(crate) fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl Mul for Microns {
+    type Output = SquareMicrons;
+    fn mul(self, rhs: Self) -> SquareMicrons {
+        SquareMicrons(self.0 as i64 * rhs.0 as i64)
+    }
+}
+
+/// Converts to square millimeters.
+impl From<SquareMicrons> for f64 {
+    fn from(other: SquareMicrons) -> Self {
+        other.as_mm2()
+    }
+}
+
+impl Add for SquareMicrons {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        SquareMicrons(self.0 + rhs.0)
+    }
+}
+
+impl Sub for SquareMicrons {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        SquareMicrons(self.0 - rhs.0)
+    }
+}
+
+impl Neg for SquareMicrons {
+    type Output = Self;
+    fn neg(self) -> Self {
+        SquareMicrons(-self.0)
+    }
+}
+
+impl Sum for SquareMicrons {
+    fn sum<I: Iterator<Item = SquareMicrons>>(iter: I) -> Self {
+        iter.fold(SquareMicrons::ZERO, |acc, a| acc + a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mul_microns() {
+        assert_eq!(Microns(2) * Microns(-3), SquareMicrons(-6));
+        assert_eq!(
+            Microns::MAX * Microns::MAX,
+            SquareMicrons(i32::MAX as i64 * i32::MAX as i64)
+        );
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(SquareMicrons(0).isqrt(), Microns(0));
+        assert_eq!(SquareMicrons(15).isqrt(), Microns(3));
+        assert_eq!(SquareMicrons(16).isqrt(), Microns(4));
+        assert_eq!(
+            (Microns(123_456) * Microns(123_456)).isqrt(),
+            Microns(123_456)
+        );
+        assert_eq!((Microns::MAX * Microns::MAX).isqrt(), Microns::MAX);
+        assert_eq!(SquareMicrons(-1).checked_isqrt(), None);
+        assert_eq!(SquareMicrons(i64::MAX).checked_isqrt(), None);
+        for n in [u128::MAX, u64::MAX as u128, (1 << 100) - 1, 1 << 100] {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1).checked_mul(r + 1).is_none_or(|sq| sq > n));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "isqrt of a negative area")]
+    fn test_isqrt_negative() {
+        let _ = SquareMicrons(-4).isqrt();
+    }
+
+    #[test]
+    fn test_into_mm2() {
+        let a: f64 = (Microns(1500) * Microns(2000)).into();
+        assert_eq!(a, 3.0);
+    }
+
+    #[test]
+    fn test_shoelace() {
+        // 2mm x 1mm rectangle
+        let pts =
+            [(0, 0), (2000, 0), (2000, 1000), (0, 1000)].map(|(x, y)| (Microns(x), Microns(y)));
+        let twice_area: SquareMicrons = (0..pts.len())
+            .map(|i| {
+                let (a, b) = (pts[i], pts[(i + 1) % pts.len()]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum();
+        assert_eq!(twice_area, SquareMicrons(4_000_000));
+        assert_eq!(f64::from(twice_area) / 2.0, 2.0);
+    }
+}
@@ -0,0 +1,106 @@
+//! Allocation-free formatting into byte buffers and embedded loggers, for
+//! firmware and serial links where `core::fmt` is too heavy.
+use crate::fixed::decimal_digits;
+use crate::FixedDecimal;
+use core::fmt;
+
+/// The output buffer can't hold the formatted value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall;
+
+impl fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "buffer too small for formatted value")
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+macro_rules! impl_ascii {
+    ($($t:ty),*) => {$(
+        impl<const SCALE: u32> FixedDecimal<SCALE, $t> {
+            /// Writes the same text as `Display`, e.g. `-12.345`, to the start
+            /// of `buf` and returns its length. `buf` is left untouched on error.
+            pub fn write_to(self, buf: &mut [u8]) -> Result<usize, BufferTooSmall> {
+                let mut digits = [0; 80];
+                let text = decimal_digits(self.0.unsigned_abs() as u128, SCALE, &mut digits);
+                let sign = usize::from(self.0 < 0);
+                let len = sign + text.len();
+                let out = buf.get_mut(..len).ok_or(BufferTooSmall)?;
+                out[..sign].fill(b'-');
+                out[sign..].copy_from_slice(text.as_bytes());
+                Ok(len)
+            }
+        }
+
+        /// Logs as millimeters with a unit, e.g. `12.345mm`.
+        #[cfg(feature = "defmt")]
+        impl<const SCALE: u32> defmt::Format for FixedDecimal<SCALE, $t> {
+            fn format(&self, f: defmt::Formatter<'_>) {
+                let mut digits = [0; 80];
+                let text = decimal_digits(self.0.unsigned_abs() as u128, SCALE, &mut digits);
+                let sign = if self.0 < 0 { "-" } else { "" };
+                defmt::write!(f, "{=str}{=str}mm", sign, text)
+            }
+        }
+
+        /// The same text as `Display`.
+        #[cfg(feature = "ufmt")]
+        impl<const SCALE: u32> ufmt::uDisplay for FixedDecimal<SCALE, $t> {
+            fn fmt<W: ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ufmt::Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                let mut digits = [0; 80];
+                let text = decimal_digits(self.0.unsigned_abs() as u128, SCALE, &mut digits);
+                if self.0 < 0 {
+                    f.write_str("-")?;
+                }
+                f.write_str(text)
+            }
+        }
+
+        /// The same text as `Debug`, e.g. `Microns(12345 = 12.345mm)`.
+        #[cfg(feature = "ufmt")]
+        impl<const SCALE: u32> ufmt::uDebug for FixedDecimal<SCALE, $t> {
+            fn fmt<W: ufmt::uWrite + ?Sized>(
+                &self,
+                f: &mut ufmt::Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                ufmt::uwrite!(f, "{}({} = {}mm)", crate::fixed::debug_name(SCALE), self.0, self)
+            }
+        }
+    )*};
+}
+impl_ascii!(i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Microns, Microns64};
+
+    #[test]
+    fn test_write_to() {
+        let mut buf = [b'x'; 24];
+        assert_eq!(Microns(-12_345).write_to(&mut buf), Ok(7));
+        assert_eq!(&buf[..8], b"-12.345x");
+        for m in [Microns::ZERO, Microns(7), Microns::MIN, Microns::MAX] {
+            let len = m.write_to(&mut buf).unwrap();
+            assert_eq!(&buf[..len], m.to_string().as_bytes());
+        }
+        let len = Microns64::MIN.write_to(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"-9223372036854775.808");
+        let mut short = [0; 5];
+        assert_eq!(Microns(-1_500).write_to(&mut short), Err(BufferTooSmall));
+        assert_eq!(short, [0; 5]);
+        assert_eq!(Microns(1_500).write_to(&mut short), Ok(5));
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt() {
+        let mut out = String::new();
+        ufmt::uwrite!(out, "{} {:?}", Microns(-12_345), Microns64::new(5)).unwrap();
+        assert_eq!(out, format!("{} {:?}", Microns(-12_345), Microns64::new(5)));
+    }
+}
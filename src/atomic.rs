@@ -0,0 +1,176 @@
+//! Microns that can be shared between threads or interrupt handlers
+//! without a lock.
+use crate::Microns;
+use core::fmt;
+use core::sync::atomic::{AtomicI32, Ordering};
+
+/// AtomicMicrons is a Microns stored in an `AtomicI32`, for publishing a
+/// position from a stepper ISR to a telemetry task. Every method is the
+/// `AtomicI32` one, in micrometers, so `fetch_add` and `fetch_sub` wrap on
+/// overflow like it does.
+///
+/// ```
+/// use core::sync::atomic::Ordering;
+/// use microns::{AtomicMicrons, Microns};
+///
+/// static POSITION: AtomicMicrons = AtomicMicrons::new(Microns::ZERO);
+///
+/// POSITION.fetch_add(Microns(25), Ordering::Relaxed);
+/// assert_eq!(POSITION.load(Ordering::Relaxed), Microns(25));
+/// ```
+#[derive(Default)]
+#[repr(transparent)]
+pub struct AtomicMicrons(AtomicI32);
+
+impl AtomicMicrons {
+    pub const fn new(m: Microns) -> Self {
+        AtomicMicrons(AtomicI32::new(m.0))
+    }
+
+    pub fn load(&self, order: Ordering) -> Microns {
+        Microns(self.0.load(order))
+    }
+
+    pub fn store(&self, m: Microns, order: Ordering) {
+        self.0.store(m.0, order);
+    }
+
+    /// Stores `m` and returns the previous value.
+    pub fn swap(&self, m: Microns, order: Ordering) -> Microns {
+        Microns(self.0.swap(m.0, order))
+    }
+
+    /// Adds `m`, wrapping on overflow, and returns the previous value.
+    pub fn fetch_add(&self, m: Microns, order: Ordering) -> Microns {
+        Microns(self.0.fetch_add(m.0, order))
+    }
+
+    /// Subtracts `m`, wrapping on overflow, and returns the previous value.
+    pub fn fetch_sub(&self, m: Microns, order: Ordering) -> Microns {
+        Microns(self.0.fetch_sub(m.0, order))
+    }
+
+    /// Stores `new` if the value is `current`. Returns the previous value,
+    /// as `Ok` if it was stored.
+    pub fn compare_exchange(
+        &self,
+        current: Microns,
+        new: Microns,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Microns, Microns> {
+        self.0
+            .compare_exchange(current.0, new.0, success, failure)
+            .map(Microns)
+            .map_err(Microns)
+    }
+
+    /// Like `compare_exchange`, but may fail spuriously; for retry loops.
+    pub fn compare_exchange_weak(
+        &self,
+        current: Microns,
+        new: Microns,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Microns, Microns> {
+        self.0
+            .compare_exchange_weak(current.0, new.0, success, failure)
+            .map(Microns)
+            .map_err(Microns)
+    }
+
+    /// Applies `f` until it's stored without interference, or `f` returns
+    /// `None`. Returns the previous value, as `Ok` if `f` was applied.
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Microns, Microns>
+    where
+        F: FnMut(Microns) -> Option<Microns>,
+    {
+        self.0
+            .fetch_update(set_order, fetch_order, |um| f(Microns(um)).map(|m| m.0))
+            .map(Microns)
+            .map_err(Microns)
+    }
+
+    /// The value, through exclusive access, so no atomic operation is
+    /// needed.
+    pub fn get_mut(&mut self) -> &mut Microns {
+        // SAFETY: `Microns` is `repr(transparent)` over `i32`
+        unsafe { &mut *(self.0.get_mut() as *mut i32).cast() }
+    }
+
+    pub fn into_inner(self) -> Microns {
+        Microns(self.0.into_inner())
+    }
+}
+
+impl From<Microns> for AtomicMicrons {
+    fn from(m: Microns) -> Self {
+        AtomicMicrons::new(m)
+    }
+}
+
+impl fmt::Debug for AtomicMicrons {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_atomic() {
+        let pos = AtomicMicrons::from(Microns(10));
+        assert_eq!(pos.swap(Microns(-5), Ordering::SeqCst), Microns(10));
+        assert_eq!(pos.fetch_sub(Microns(5), Ordering::SeqCst), Microns(-5));
+        let stale =
+            pos.compare_exchange(Microns(0), Microns(1), Ordering::SeqCst, Ordering::SeqCst);
+        assert_eq!(stale, Err(Microns(-10)));
+        let swapped = pos.compare_exchange(
+            Microns(-10),
+            Microns::MAX,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        );
+        assert_eq!(swapped, Ok(Microns(-10)));
+        pos.fetch_add(Microns(1), Ordering::SeqCst);
+        assert_eq!(pos.load(Ordering::SeqCst), Microns::MIN);
+        let saturating = |m: Microns| m.checked_sub(Microns(1));
+        assert_eq!(
+            pos.fetch_update(Ordering::SeqCst, Ordering::SeqCst, saturating),
+            Err(Microns::MIN)
+        );
+        let mut pos = pos;
+        *pos.get_mut() += Microns(7);
+        assert_eq!(
+            format!("{pos:?}"),
+            format!("{:?}", Microns::MIN + Microns(7))
+        );
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let pos = Arc::new(AtomicMicrons::default());
+        let steppers: Vec<_> = (0..4)
+            .map(|_| {
+                let pos = Arc::clone(&pos);
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        pos.fetch_add(Microns(3), Ordering::Relaxed);
+                    }
+                })
+            })
+            .collect();
+        for s in steppers {
+            s.join().unwrap();
+        }
+        assert_eq!(Arc::into_inner(pos).unwrap().into_inner(), Microns(12_000));
+    }
+}
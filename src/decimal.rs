@@ -0,0 +1,59 @@
+//! Conversions to and from `rust_decimal::Decimal` millimeters.
+//!
+//! Both are decimal, so there's no float round trip: a Microns is exactly a
+//! `Decimal` with three places, and a `Decimal` with more places is
+//! truncated toward zero like `FromStr`.
+use crate::{ConversionError, Microns};
+use rust_decimal::Decimal;
+
+/// Exact, with a scale of three: `Microns(1250)` is `1.250`.
+impl From<Microns> for Decimal {
+    fn from(m: Microns) -> Self {
+        Decimal::new(m.0 as i64, 3)
+    }
+}
+
+/// Truncates toward zero to whole micrometers.
+impl TryFrom<Decimal> for Microns {
+    type Error = ConversionError;
+    fn try_from(mm: Decimal) -> Result<Self, ConversionError> {
+        let (mantissa, scale) = (mm.mantissa(), mm.scale());
+        // a mantissa is at most 96 bits, so scaling up by 1000 can't overflow
+        let um = match scale {
+            0..=3 => mantissa * 10i128.pow(3 - scale),
+            _ => mantissa / 10i128.pow(scale - 3),
+        };
+        crate::exact::narrow::<3, i32>(um)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_decimal_conversions() {
+        let d = |s| Decimal::from_str(s).unwrap();
+        assert_eq!(Decimal::from(Microns(-1250)), d("-1.250"));
+        assert_eq!(Decimal::from(Microns(-1250)).to_string(), "-1.250");
+        assert_eq!(Microns::try_from(d("1.25")), Ok(Microns(1250)));
+        assert_eq!(Microns::try_from(d("12")), Ok(Microns(12_000)));
+        assert_eq!(Microns::try_from(d("-0.0019")), Ok(Microns(-1)));
+        assert_eq!(
+            Microns::try_from(d("0.0000000000000000000000000001")),
+            Ok(Microns::ZERO)
+        );
+        for m in [Microns::MIN, Microns::MAX] {
+            assert_eq!(Microns::try_from(Decimal::from(m)), Ok(m));
+        }
+        assert_eq!(
+            Microns::try_from(Decimal::MAX),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Microns::try_from(d("-2147484")),
+            Err(ConversionError::Underflow)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Removing near-duplicate points from toolpaths and point sets.
+//!
+//! Distances are compared exactly on the integer coordinates, so a point
+//! is dropped only if it's strictly closer than `tol` to one that's kept.
+use crate::{geom::MicronPoint, Microns};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+/// Removes points closer than `tol` to the last point kept, so each run of
+/// near-coincident points collapses to its first. Measuring from the kept
+/// point rather than the previous one means a slow drift still leaves a
+/// point every `tol`.
+///
+/// ```
+/// use microns::{dedup_within, Microns, Point2};
+///
+/// let p = |x| Point2::new(Microns(x), Microns(0));
+/// let mut path = vec![p(0), p(2), p(4), p(6), p(6), p(20), p(0)];
+/// dedup_within(&mut path, Microns(5));
+/// assert_eq!(path, [p(0), p(6), p(20), p(0)]);
+/// ```
+pub fn dedup_within<P: MicronPoint>(points: &mut Vec<P>, tol: Microns) {
+    if tol <= Microns::ZERO {
+        return;
+    }
+    let tol_sq = (tol.0 as i128).pow(2);
+    let mut last: Option<P> = None;
+    points.retain(|p| match last {
+        Some(kept) if kept.distance_sq(p) < tol_sq => false,
+        _ => {
+            last = Some(*p);
+            true
+        }
+    });
+}
+
+/// Removes every point closer than `tol` to an earlier point that was kept,
+/// wherever it is in the sequence. Points are bucketed into cells `tol`
+/// wide, so this runs in roughly linear time.
+#[cfg(feature = "std")]
+pub fn dedup_unordered_within<P: MicronPoint>(points: &mut Vec<P>, tol: Microns) {
+    if tol <= Microns::ZERO {
+        return;
+    }
+    let tol_sq = (tol.0 as i128).pow(2);
+    let cell_of = |p: &P| {
+        let mut cell = [0i64; 3];
+        for (axis, c) in cell.iter_mut().enumerate().take(P::DIM) {
+            *c = (p.coord(axis).0 as i64).div_euclid(tol.0 as i64);
+        }
+        cell
+    };
+    let mut kept: HashMap<[i64; 3], Vec<P>> = HashMap::new();
+    points.retain(|p| {
+        let cell = cell_of(p);
+        // anything within `tol` is in this cell or one next to it
+        let near = (0..3usize.pow(P::DIM as u32)).any(|n| {
+            let mut neighbor = cell;
+            for (axis, c) in neighbor.iter_mut().enumerate().take(P::DIM) {
+                *c += (n / 3usize.pow(axis as u32) % 3) as i64 - 1;
+            }
+            kept.get(&neighbor)
+                .is_some_and(|pts| pts.iter().any(|q| q.distance_sq(p) < tol_sq))
+        });
+        if !near {
+            kept.entry(cell).or_default().push(*p);
+        }
+        !near
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point2;
+
+    #[test]
+    fn test_dedup_within() {
+        let p = |x, y| Point2::new(Microns(x), Microns(y));
+        let mut path = vec![p(0, 0), p(3, 4), p(3, 3), p(6, 8), p(6, 8)];
+        let original = path.clone();
+        dedup_within(&mut path, Microns(5));
+        assert_eq!(path, [p(0, 0), p(3, 4), p(6, 8)]);
+        let mut exact = original.clone();
+        dedup_within(&mut exact, Microns(1));
+        assert_eq!(exact, original[..4]);
+        let mut untouched = original.clone();
+        dedup_within(&mut untouched, Microns(0));
+        assert_eq!(untouched, original);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_dedup_unordered_within() {
+        use crate::Point3;
+
+        let p = |x, y, z| Point3::new(Microns(x), Microns(y), Microns(z));
+        let mut cloud = vec![
+            p(0, 0, 0),
+            p(100, 0, 0),
+            p(-9, 0, 0),
+            p(9, 9, 9),
+            p(101, 1, -1),
+            p(-10, 0, 0),
+            p(0, 0, 0),
+        ];
+        dedup_unordered_within(&mut cloud, Microns(10));
+        assert_eq!(cloud, [p(0, 0, 0), p(100, 0, 0), p(9, 9, 9), p(-10, 0, 0)]);
+
+        let far = Point2::new(Microns::MIN, Microns::MAX);
+        let mut edges = vec![far, Point2::new(Microns::MAX, Microns::MIN), far];
+        dedup_unordered_within(&mut edges, Microns::MAX);
+        assert_eq!(edges.len(), 2);
+    }
+}
@@ -0,0 +1,57 @@
+//! `diesel` column support: Microns is an `Integer` column and Microns64 a
+//! `BigInt`, each holding the raw count. The `AsExpression` and
+//! `FromSqlRow` derives sit on `FixedDecimal` itself.
+use crate::FixedDecimal;
+use ::diesel::backend::Backend;
+use ::diesel::deserialize::{self, FromSql};
+use ::diesel::serialize::{self, Output, ToSql};
+use ::diesel::sql_types::{BigInt, Integer};
+
+macro_rules! impl_diesel {
+    ($($t:ty => $sql:ty),*) => {$(
+        impl<const SCALE: u32, DB: Backend> ToSql<$sql, DB> for FixedDecimal<SCALE, $t>
+        where
+            $t: ToSql<$sql, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                <$t as ToSql<$sql, DB>>::to_sql(&self.0, out)
+            }
+        }
+
+        impl<const SCALE: u32, DB: Backend> FromSql<$sql, DB> for FixedDecimal<SCALE, $t>
+        where
+            $t: FromSql<$sql, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                <$t as FromSql<$sql, DB>>::from_sql(bytes).map(FixedDecimal)
+            }
+        }
+    )*};
+}
+impl_diesel!(i32 => Integer, i64 => BigInt);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Microns, Microns64};
+    use ::diesel::prelude::*;
+    use ::diesel::sql_types::{BigInt, Integer};
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        let um: Microns = ::diesel::select(Microns(-1250).into_sql::<Integer>())
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(um, Microns(-1250));
+        let long = Microns64::new(5_000_000_000);
+        let back: Microns64 = ::diesel::select(long.into_sql::<BigInt>())
+            .get_result(&mut conn)
+            .unwrap();
+        assert_eq!(back, long);
+        let sum: Option<Microns> =
+            ::diesel::select((Microns(1).into_sql::<Integer>() + Microns(2)).nullable())
+                .get_result(&mut conn)
+                .unwrap();
+        assert_eq!(sum, Some(Microns(3)));
+    }
+}
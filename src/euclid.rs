@@ -0,0 +1,72 @@
+//! Conversions to and from `euclid` types tagged with the `Micron` unit.
+//!
+//! ```
+//! use euclid::{Length, Point2D};
+//! use microns::{euclid::Micron, Microns, Point2};
+//!
+//! let width: Length<i32, Micron> = Microns(1500).into();
+//! assert_eq!(width.get(), 1500);
+//! let p: Point2D<i32, Micron> = Point2::new(Microns(1), Microns(2)).into();
+//! assert_eq!(Point2::from(p + euclid::vec2(1, 1)), Point2::new(Microns(2), Microns(3)));
+//! ```
+use crate::{Microns, Point2, Point3, Vec2, Vec3};
+use ::euclid::{Length, Point2D, Point3D, Vector2D, Vector3D};
+
+/// The euclid unit tag for values counted in micrometers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Micron {}
+
+impl From<Microns> for Length<i32, Micron> {
+    fn from(m: Microns) -> Self {
+        Length::new(m.0)
+    }
+}
+
+impl From<Length<i32, Micron>> for Microns {
+    fn from(l: Length<i32, Micron>) -> Self {
+        Microns(l.get())
+    }
+}
+
+macro_rules! impl_euclid {
+    ($($ours:ident, $theirs:ident { $($c:ident),* };)*) => {$(
+        impl From<$ours> for $theirs<i32, Micron> {
+            fn from(p: $ours) -> Self {
+                $theirs::new($(p.$c.0),*)
+            }
+        }
+
+        impl From<$theirs<i32, Micron>> for $ours {
+            fn from(p: $theirs<i32, Micron>) -> Self {
+                $ours { $($c: Microns(p.$c)),* }
+            }
+        }
+    )*};
+}
+impl_euclid! {
+    Point2, Point2D { x, y };
+    Point3, Point3D { x, y, z };
+    Vec2, Vector2D { x, y };
+    Vec3, Vector3D { x, y, z };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_euclid_conversions() {
+        let len = Length::<i32, Micron>::from(Microns::MIN);
+        assert_eq!(Microns::from(len), Microns::MIN);
+        let p = Point3::new(Microns(1), Microns(-2), Microns(3));
+        let typed = Point3D::<i32, Micron>::from(p);
+        assert_eq!((typed.x, typed.y, typed.z), (1, -2, 3));
+        assert_eq!(Point3::from(typed), p);
+        let v = Vec2::new(Microns(5), Microns(6));
+        assert_eq!(Vec2::from(Vector2D::<i32, Micron>::from(v) * 2), v + v);
+        assert_eq!(
+            Vec3::from(Vector3D::<i32, Micron>::new(7, 8, 9)),
+            Vec3::new(Microns(7), Microns(8), Microns(9))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Exact arithmetic between integers and float factors.
+//!
+//! Every finite `f32` is `mantissa * 2^exp` for integers that fit in an
+//! `i128` product, so scaling by one can be done without any intermediate
+//! rounding; the only rounding is the final one chosen by the caller.
+use crate::{ConversionError, FixedDecimal, RoundingMode};
+
+/// Splits a finite float into `mantissa * 2^exp`.
+fn decompose(val: f32) -> (i128, i32) {
+    let bits = val.to_bits();
+    let exp_bits = ((bits >> 23) & 0xff) as i32;
+    let frac = (bits & 0x7f_ffff) as i128;
+    let (mantissa, exp) = if exp_bits == 0 {
+        (frac, -149)
+    } else {
+        (frac | 0x80_0000, exp_bits - 150)
+    };
+    if val.is_sign_negative() {
+        (-mantissa, exp)
+    } else {
+        (mantissa, exp)
+    }
+}
+
+/// The error for a result that is infinitely large with the given sign.
+fn unbounded(negative: bool) -> ConversionError {
+    if negative {
+        ConversionError::Underflow
+    } else {
+        ConversionError::Overflow
+    }
+}
+
+/// `val << shift`, where anything that would leave the `i128` range is far
+/// outside every range results are narrowed to.
+fn shl(val: i128, shift: u32) -> Result<i128, ConversionError> {
+    if val != 0 && val.unsigned_abs().leading_zeros() <= shift + 1 {
+        return Err(unbounded(val < 0));
+    }
+    Ok(val << shift)
+}
+
+/// `val * factor`, rounded once with `mode`. `val` must fit in an `i64`.
+pub(crate) fn mul(val: i128, factor: f32, mode: RoundingMode) -> Result<i128, ConversionError> {
+    mul_add(val, factor, 0, mode)
+}
+
+/// `val * factor + addend`, rounded once with `mode`. `val` and `addend`
+/// must fit in an `i64`.
+pub(crate) fn mul_add(
+    val: i128,
+    factor: f32,
+    addend: i128,
+    mode: RoundingMode,
+) -> Result<i128, ConversionError> {
+    if factor.is_nan() {
+        return Err(ConversionError::NaN);
+    }
+    if factor.is_infinite() {
+        return match val {
+            0 => Err(ConversionError::NaN),
+            _ => Err(unbounded((val < 0) != (factor < 0.0))),
+        };
+    }
+    let (mantissa, exp) = decompose(factor);
+    let product = val * mantissa;
+    if exp >= 0 {
+        return Ok(shl(product, exp as u32)? + addend);
+    }
+    // |product| < 2^88, so past 2^-100 the fraction is a non-tie in (-1, 1)
+    // that rounds the same way regardless of how much further it's shifted
+    let (whole, frac, den) = if -exp > 100 {
+        (0, product.signum(), 1 << 100)
+    } else {
+        let den = 1 << -exp;
+        (product / den, product % den, den)
+    };
+    Ok(mode.round_parts(addend + whole, frac, den))
+}
+
+/// `val / divisor`, rounded once with `mode`. `val` must fit in an `i64`.
+pub(crate) fn div(val: i128, divisor: f32, mode: RoundingMode) -> Result<i128, ConversionError> {
+    if divisor.is_nan() {
+        return Err(ConversionError::NaN);
+    }
+    if divisor.is_infinite() {
+        return Ok(0);
+    }
+    let (mantissa, exp) = decompose(divisor);
+    if mantissa == 0 {
+        return match val {
+            0 => Err(ConversionError::NaN),
+            _ => Err(unbounded((val < 0) != divisor.is_sign_negative())),
+        };
+    }
+    if val == 0 {
+        return Ok(0);
+    }
+    if exp >= 0 {
+        // |val| < 2^64, so past 2^100 the quotient is a non-tie in (-1, 1)
+        let den = mantissa << exp.min(100);
+        return Ok(mode.div_i128(val, den));
+    }
+    // the numerator is only out of i128 range if the quotient is too
+    let num = shl(val, -exp as u32).map_err(|_| unbounded((val < 0) != (mantissa < 0)))?;
+    Ok(mode.div_i128(num, mantissa))
+}
+
+/// Narrows an exact result back to a FixedDecimal such as Microns.
+pub(crate) fn narrow<const SCALE: u32, T: TryFrom<i128>>(
+    val: i128,
+) -> Result<FixedDecimal<SCALE, T>, ConversionError> {
+    match T::try_from(val) {
+        Ok(v) => Ok(FixedDecimal(v)),
+        Err(_) => Err(unbounded(val < 0)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decompose() {
+        for val in [1.0f32, -1.25, 0.1, 3e-40, -7e30, 0.0] {
+            let (m, e) = decompose(val);
+            assert_eq!(m as f64 * 2f64.powi(e), val as f64);
+        }
+    }
+
+    #[test]
+    fn test_mul() {
+        use RoundingMode::*;
+        assert_eq!(mul(3, 0.5, Trunc), Ok(1));
+        assert_eq!(mul(3, 0.5, HalfEven), Ok(2));
+        assert_eq!(mul(-3, 0.5, Floor), Ok(-2));
+        assert_eq!(mul(1, 1e-40, Ceil), Ok(1));
+        assert_eq!(
+            mul(1, 1e30, Trunc).and_then(narrow::<3, i32>),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            mul(-1, f32::INFINITY, Trunc),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(mul(0, f32::INFINITY, Trunc), Err(ConversionError::NaN));
+        assert_eq!(mul(5, f32::NAN, Trunc), Err(ConversionError::NaN));
+    }
+
+    #[test]
+    fn test_mul_add() {
+        use RoundingMode::*;
+        assert_eq!(mul_add(-3, 0.5, 2, Trunc), Ok(0));
+        assert_eq!(mul_add(-3, 0.5, 1, Trunc), Ok(0));
+        assert_eq!(mul_add(-3, 0.5, 1, HalfUp), Ok(-1));
+        assert_eq!(mul_add(-1, 1e-40, 10, Trunc), Ok(9));
+        assert_eq!(mul_add(-1, 1e-40, -10, Trunc), Ok(-10));
+        assert_eq!(mul_add(3, 4.0, -2, Trunc), Ok(10));
+    }
+
+    #[test]
+    fn test_div() {
+        use RoundingMode::*;
+        assert_eq!(div(3, 2.0, Trunc), Ok(1));
+        assert_eq!(div(3, 0.5, Trunc), Ok(6));
+        assert_eq!(div(-3, 2.0, Floor), Ok(-2));
+        assert_eq!(div(1, 1e30, Ceil), Ok(1));
+        assert_eq!(div(1, f32::INFINITY, Trunc), Ok(0));
+        assert_eq!(
+            div(1, 1e-30, Trunc).and_then(narrow::<3, i32>),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(div(1, -0.0, Trunc), Err(ConversionError::Underflow));
+        assert_eq!(div(0, 0.0, Trunc), Err(ConversionError::NaN));
+    }
+}
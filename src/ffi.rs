@@ -0,0 +1,197 @@
+//! A C ABI for firmware and CAM plugins, laid out for `cbindgen`.
+//!
+//! `Microns` is `#[repr(transparent)]`, so it crosses the boundary as a plain
+//! `int32_t` micrometer count. Functions that can fail return a
+//! `MicronsStatus` and write their result through an out pointer, which is
+//! left untouched on error.
+use crate::{ConversionError, Microns};
+use core::ffi::{c_char, CStr};
+
+/// The result of a fallible `microns_*` call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MicronsStatus {
+    Ok = 0,
+    NaN = 1,
+    Overflow = 2,
+    Underflow = 3,
+    /// A pointer argument was null.
+    NullPointer = 4,
+    /// The output buffer can't hold the text and its terminating NUL.
+    BufferTooSmall = 5,
+    /// The string isn't valid UTF-8 or isn't a length `FromStr` accepts.
+    ParseError = 6,
+}
+
+impl From<ConversionError> for MicronsStatus {
+    fn from(e: ConversionError) -> Self {
+        match e {
+            ConversionError::NaN => MicronsStatus::NaN,
+            ConversionError::Overflow => MicronsStatus::Overflow,
+            ConversionError::Underflow => MicronsStatus::Underflow,
+        }
+    }
+}
+
+/// Stores `result` in `out` if both are present.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn store(out: *mut Microns, result: Result<Microns, MicronsStatus>) -> MicronsStatus {
+    if out.is_null() {
+        return MicronsStatus::NullPointer;
+    }
+    match result {
+        Ok(m) => {
+            out.write(m);
+            MicronsStatus::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Converts millimeters to Microns, truncating toward zero like
+/// `Microns::try_from(f64)`.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn microns_from_mm(mm: f64, out: *mut Microns) -> MicronsStatus {
+    store(out, Microns::try_from(mm).map_err(MicronsStatus::from))
+}
+
+/// The value in millimeters.
+#[no_mangle]
+pub extern "C" fn microns_to_mm(m: Microns) -> f64 {
+    f64::from(m)
+}
+
+/// `a + b`, or `Overflow`/`Underflow` if the sum is out of range.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn microns_add_checked(
+    a: Microns,
+    b: Microns,
+    out: *mut Microns,
+) -> MicronsStatus {
+    let sum = a.checked_add(b).ok_or(if b.0 > 0 {
+        MicronsStatus::Overflow
+    } else {
+        MicronsStatus::Underflow
+    });
+    store(out, sum)
+}
+
+/// Parses a NUL-terminated string the way `FromStr` does, so `"1.5"`,
+/// `"1.5mm"` and `"250um"` are all accepted.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string, and `out` must be
+/// null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn microns_parse(s: *const c_char, out: *mut Microns) -> MicronsStatus {
+    if s.is_null() {
+        return MicronsStatus::NullPointer;
+    }
+    let parsed = CStr::from_ptr(s)
+        .to_str()
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .ok_or(MicronsStatus::ParseError);
+    store(out, parsed)
+}
+
+/// Writes the same text as `Display`, e.g. `-12.345`, to `buf` with a
+/// terminating NUL. If `written` isn't null it receives the text length,
+/// not counting the NUL. Thirteen bytes are always enough.
+///
+/// # Safety
+///
+/// `buf` must be null or valid for `len` bytes of writes, and `written`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn microns_format(
+    m: Microns,
+    buf: *mut c_char,
+    len: usize,
+    written: *mut usize,
+) -> MicronsStatus {
+    if buf.is_null() {
+        return MicronsStatus::NullPointer;
+    }
+    let buf = core::slice::from_raw_parts_mut(buf.cast::<u8>(), len);
+    match len.checked_sub(1).map(|n| m.write_to(&mut buf[..n])) {
+        Some(Ok(n)) => {
+            buf[n] = 0;
+            if !written.is_null() {
+                written.write(n);
+            }
+            MicronsStatus::Ok
+        }
+        _ => MicronsStatus::BufferTooSmall,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let mut out = Microns(7);
+        unsafe {
+            assert_eq!(microns_from_mm(-1.2345, &mut out), MicronsStatus::Ok);
+            assert_eq!(out, Microns(-1234));
+            assert_eq!(microns_from_mm(f64::NAN, &mut out), MicronsStatus::NaN);
+            assert_eq!(microns_from_mm(1e10, &mut out), MicronsStatus::Overflow);
+            assert_eq!(out, Microns(-1234));
+            let null = core::ptr::null_mut();
+            assert_eq!(microns_from_mm(1.0, null), MicronsStatus::NullPointer);
+            assert_eq!(
+                microns_add_checked(Microns::MAX, Microns(1), &mut out),
+                MicronsStatus::Overflow
+            );
+            assert_eq!(
+                microns_add_checked(Microns::MIN, Microns(-1), &mut out),
+                MicronsStatus::Underflow
+            );
+            assert_eq!(
+                microns_add_checked(Microns(2), Microns(-3), &mut out),
+                MicronsStatus::Ok
+            );
+            assert_eq!(out, Microns(-1));
+            assert_eq!(
+                microns_parse(c"250um".as_ptr(), &mut out),
+                MicronsStatus::Ok
+            );
+            assert_eq!(out, Microns(250));
+            assert_eq!(
+                microns_parse(c"1ft".as_ptr(), &mut out),
+                MicronsStatus::ParseError
+            );
+        }
+        assert_eq!(microns_to_mm(Microns(1250)), 1.25);
+    }
+
+    #[test]
+    fn test_format() {
+        let mut buf = [1 as c_char; 13];
+        let mut written = 0;
+        unsafe {
+            let status = microns_format(Microns::MIN, buf.as_mut_ptr(), buf.len(), &mut written);
+            assert_eq!(status, MicronsStatus::Ok);
+            assert_eq!(CStr::from_ptr(buf.as_ptr()), c"-2147483.648");
+            assert_eq!(written, 12);
+            let status = microns_format(Microns(5), buf.as_mut_ptr(), 5, core::ptr::null_mut());
+            assert_eq!(status, MicronsStatus::BufferTooSmall);
+            let status = microns_format(Microns(5), buf.as_mut_ptr(), 0, &mut written);
+            assert_eq!(status, MicronsStatus::BufferTooSmall);
+        }
+    }
+}
@@ -0,0 +1,1073 @@
+//! The fixed-decimal core that Microns is built on.
+//!
+//! `FixedDecimal<SCALE, T>` counts steps of `10^-SCALE` millimeters in a `T`,
+//! so `Microns` is `FixedDecimal<3, i32>`. Other resolutions are just other
+//! aliases, and share everything here that doesn't depend on the scale:
+//!
+//! ```
+//! use microns::FixedDecimal;
+//!
+//! /// Tenths of a micrometer.
+//! type TenthMicrons = FixedDecimal<4, i32>;
+//!
+//! let a = TenthMicrons::from(0.25f32);
+//! assert_eq!(a.0, 2_500);
+//! assert_eq!(a + TenthMicrons::EPSILON, FixedDecimal(2_501));
+//! assert_eq!(f64::from(a * 2), 0.5);
+//! ```
+//!
+//! Floats are read as millimeters and truncated toward zero, the same as
+//! `Microns::from(f32)`.
+use crate::{exact, float, Checked, ConversionError, RoundingMode, Saturating};
+use core::fmt;
+use core::iter::Sum;
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, RangeBounds, Rem, Sub, SubAssign,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(
+        zerocopy::FromBytes,
+        zerocopy::IntoBytes,
+        zerocopy::Immutable,
+        zerocopy::KnownLayout,
+        zerocopy::Unaligned
+    )
+)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Integer),
+    diesel(sql_type = diesel::sql_types::BigInt)
+)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+/// FixedDecimal holds a whole number of `10^-SCALE` mm steps, laid out
+/// exactly like the bare `T`.
+pub struct FixedDecimal<const SCALE: u32, T>(pub T);
+
+// SAFETY: `repr(transparent)` over `T`, so every bit pattern of `T` is a
+// valid value and there is no padding.
+#[cfg(feature = "bytemuck")]
+unsafe impl<const SCALE: u32, T: bytemuck::Zeroable> bytemuck::Zeroable for FixedDecimal<SCALE, T> {}
+#[cfg(feature = "bytemuck")]
+unsafe impl<const SCALE: u32, T: bytemuck::Pod> bytemuck::Pod for FixedDecimal<SCALE, T> {}
+
+/// The name `Debug` prints. Three places is what this crate is about.
+pub(crate) const fn debug_name(scale: u32) -> &'static str {
+    match scale {
+        3 => "Microns",
+        _ => "FixedDecimal",
+    }
+}
+
+/// Shows the raw count next to the millimeters it stands for, e.g.
+/// `Microns(12345 = 12.345mm)`.
+impl<const SCALE: u32, T: Copy + Into<i128>> fmt::Debug for FixedDecimal<SCALE, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let raw: i128 = self.0.into();
+        write!(f, "{}({} = {}mm)", debug_name(SCALE), raw, self)
+    }
+}
+
+// The archived form stores the integer little-endian so it can be read in
+// place; these bring it back to a native value.
+#[cfg(feature = "rkyv")]
+macro_rules! impl_archived {
+    ($($t:ty),*) => {$(
+        impl<const SCALE: u32> ArchivedFixedDecimal<SCALE, $t> {
+            pub fn to_native(&self) -> FixedDecimal<SCALE, $t> {
+                FixedDecimal(self.0.to_native())
+            }
+        }
+
+        impl<const SCALE: u32> fmt::Debug for ArchivedFixedDecimal<SCALE, $t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_native(), f)
+            }
+        }
+    )*};
+}
+#[cfg(feature = "rkyv")]
+impl_archived!(i32, i64);
+
+/// Writes `abs` as a decimal with `scale` places into the end of `buf` and
+/// returns the text.
+pub(crate) fn decimal_digits(abs: u128, scale: u32, buf: &mut [u8; 80]) -> &str {
+    assert!(scale <= 40, "scale too large to format");
+    let mut pos = buf.len();
+    let mut rest = abs;
+    for _ in 0..scale {
+        pos -= 1;
+        buf[pos] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+    }
+    if scale > 0 {
+        pos -= 1;
+        buf[pos] = b'.';
+    }
+    loop {
+        pos -= 1;
+        buf[pos] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    core::str::from_utf8(&buf[pos..]).expect("digits are ASCII")
+}
+
+/// The value in millimeters with exactly `SCALE` decimals, never in
+/// scientific notation: `Microns(12345)` prints as `12.345`. Width, fill
+/// and `+` are honored; precision is not.
+impl<const SCALE: u32, T: Copy + Into<i128>> fmt::Display for FixedDecimal<SCALE, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let val: i128 = self.0.into();
+        let mut buf = [0; 80];
+        f.pad_integral(
+            val >= 0,
+            "",
+            decimal_digits(val.unsigned_abs(), SCALE, &mut buf),
+        )
+    }
+}
+
+/// How close, in ulps of the result, a float count of steps must be to a
+/// whole step to be taken as exact. A decimal scaled by a power of ten or
+/// by 25.4 picks up an ulp or two of error, much less than a step.
+const SNAP_ULPS: f64 = 4.0;
+
+// One impl per backing integer, generic over the scale. Written as a macro
+// rather than over a trait so that the methods can stay `const fn`.
+macro_rules! impl_fixed {
+    ($t:ty, $wide:ty) => {
+        impl<const SCALE: u32> FixedDecimal<SCALE, $t> {
+            pub const ZERO: Self = FixedDecimal(0);
+            pub const MIN: Self = FixedDecimal(<$t>::MIN);
+            pub const MAX: Self = FixedDecimal(<$t>::MAX);
+            /// The smallest representable step.
+            pub const EPSILON: Self = FixedDecimal(1);
+            pub const MM: Self = FixedDecimal(<$t>::pow(10, SCALE));
+            pub const CM: Self = FixedDecimal(<$t>::pow(10, SCALE + 1));
+            pub const M: Self = FixedDecimal(<$t>::pow(10, SCALE + 3));
+
+            pub const fn new(val: $t) -> Self {
+                FixedDecimal(val)
+            }
+
+            /// Steps per millimeter, exact as a float for any scale that fits.
+            const PER_MM: f32 = <$t>::pow(10, SCALE) as f32;
+
+            pub const fn abs(&self) -> Self {
+                FixedDecimal(self.0.abs())
+            }
+
+            /// `self + rhs`, usable in const contexts. Same as the `+` operator.
+            #[allow(clippy::should_implement_trait)]
+            pub const fn add(self, rhs: Self) -> Self {
+                FixedDecimal(self.0 + rhs.0)
+            }
+
+            /// `self - rhs`, usable in const contexts. Same as the `-` operator.
+            #[allow(clippy::should_implement_trait)]
+            pub const fn sub(self, rhs: Self) -> Self {
+                FixedDecimal(self.0 - rhs.0)
+            }
+
+            pub const fn min(self, other: Self) -> Self {
+                if self.0 <= other.0 {
+                    self
+                } else {
+                    other
+                }
+            }
+
+            pub const fn max(self, other: Self) -> Self {
+                if self.0 >= other.0 {
+                    self
+                } else {
+                    other
+                }
+            }
+
+            /// Restricts `self` to `lo..=hi`. Panics if `lo > hi`.
+            pub const fn clamp(self, lo: Self, hi: Self) -> Self {
+                assert!(lo.0 <= hi.0, "clamp with lo > hi");
+                self.max(lo).min(hi)
+            }
+
+            /// The point halfway between `self` and `other`, rounded toward zero.
+            /// Never overflows, unlike `(a + b) / 2`.
+            pub const fn midpoint(self, other: Self) -> Self {
+                FixedDecimal(((self.0 as $wide + other.0 as $wide) / 2) as $t)
+            }
+
+            /// The distance between `self` and `other`. Differences too large
+            /// for the type (only possible between values of opposite sign
+            /// near the ends of the range) saturate to `MAX`.
+            pub const fn abs_diff(self, other: Self) -> Self {
+                let diff = self.0.abs_diff(other.0);
+                if diff > <$t>::MAX.unsigned_abs() {
+                    Self::MAX
+                } else {
+                    FixedDecimal(diff as $t)
+                }
+            }
+
+            /// Whether `self` and `other` are at most `tol` apart, inclusive.
+            /// The sign of `tol` is ignored.
+            pub const fn approx_eq(self, other: Self, tol: Self) -> bool {
+                self.0.abs_diff(other.0) <= tol.0.unsigned_abs()
+            }
+
+            /// Whether `self` lies in `range`, e.g. `m.within(lo..=hi)`.
+            pub fn within(self, range: impl RangeBounds<Self>) -> bool {
+                range.contains(&self)
+            }
+
+            /// `-1`, `0` or `1` depending on the sign of `self`.
+            pub const fn signum(self) -> $t {
+                self.0.signum()
+            }
+
+            pub const fn is_positive(self) -> bool {
+                self.0 > 0
+            }
+
+            pub const fn is_negative(self) -> bool {
+                self.0 < 0
+            }
+
+            pub const fn is_zero(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Returns `None` instead of overflowing.
+            pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_add(rhs.0) {
+                    Some(val) => Some(FixedDecimal(val)),
+                    None => None,
+                }
+            }
+
+            pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+                match self.0.checked_sub(rhs.0) {
+                    Some(val) => Some(FixedDecimal(val)),
+                    None => None,
+                }
+            }
+
+            /// Returns `None` for `MIN`, which has no positive counterpart.
+            pub const fn checked_neg(self) -> Option<Self> {
+                match self.0.checked_neg() {
+                    Some(val) => Some(FixedDecimal(val)),
+                    None => None,
+                }
+            }
+
+            /// Panics on overflow, even in release builds.
+            #[track_caller]
+            pub fn strict_add(self, rhs: Self) -> Self {
+                self.checked_add(rhs).expect("attempt to add with overflow")
+            }
+
+            #[track_caller]
+            pub fn strict_sub(self, rhs: Self) -> Self {
+                self.checked_sub(rhs)
+                    .expect("attempt to subtract with overflow")
+            }
+
+            #[track_caller]
+            pub fn strict_neg(self) -> Self {
+                self.checked_neg().expect("attempt to negate with overflow")
+            }
+
+            /// Clamps to `MIN`/`MAX` instead of overflowing.
+            pub const fn saturating_add(self, rhs: Self) -> Self {
+                FixedDecimal(self.0.saturating_add(rhs.0))
+            }
+
+            pub const fn saturating_sub(self, rhs: Self) -> Self {
+                FixedDecimal(self.0.saturating_sub(rhs.0))
+            }
+
+            /// Wraps around at the boundary of the backing integer, in debug
+            /// and release builds alike.
+            pub const fn wrapping_add(self, rhs: Self) -> Self {
+                FixedDecimal(self.0.wrapping_add(rhs.0))
+            }
+
+            pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                FixedDecimal(self.0.wrapping_sub(rhs.0))
+            }
+
+            pub const fn wrapping_neg(self) -> Self {
+                FixedDecimal(self.0.wrapping_neg())
+            }
+
+            /// Returns the wrapped result along with whether an overflow occurred.
+            pub const fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+                let (val, overflowed) = self.0.overflowing_add(rhs.0);
+                (FixedDecimal(val), overflowed)
+            }
+
+            pub const fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+                let (val, overflowed) = self.0.overflowing_sub(rhs.0);
+                (FixedDecimal(val), overflowed)
+            }
+
+            /// Whether `self` is a whole number of `step`s. Only zero is a
+            /// multiple of a zero step.
+            pub const fn is_multiple_of(self, step: Self) -> bool {
+                self.0.unsigned_abs().is_multiple_of(step.0.unsigned_abs())
+            }
+
+            /// How many whole `rhs` fit in `self` and what's left over, both
+            /// truncated toward zero like `/` and `%` on integers.
+            pub const fn div_rem(self, rhs: Self) -> ($t, Self) {
+                (self.0 / rhs.0, FixedDecimal(self.0 % rhs.0))
+            }
+
+            /// Number of whole `rhs` steps below `self`, rounding toward
+            /// negative infinity for positive `rhs`. Pairs with `rem_euclid`.
+            pub const fn div_euclid(self, rhs: Self) -> $t {
+                self.0.div_euclid(rhs.0)
+            }
+
+            /// The remainder of `div_euclid`, always in `0..rhs.abs()`.
+            pub const fn rem_euclid(self, rhs: Self) -> Self {
+                FixedDecimal(self.0.rem_euclid(rhs.0))
+            }
+
+            /// `sqrt(self² + other²)` in integer math, rounded down.
+            /// Panics if the result doesn't fit.
+            pub fn hypot(self, other: Self) -> Self {
+                Self::hypot_n(&[self, other])
+            }
+
+            /// `sqrt(self² + b² + c²)` in integer math, rounded down.
+            /// Panics if the result doesn't fit.
+            pub fn hypot3(self, b: Self, c: Self) -> Self {
+                Self::hypot_n(&[self, b, c])
+            }
+
+            fn hypot_n(parts: &[Self]) -> Self {
+                let sum: u128 = parts
+                    .iter()
+                    .map(|m| (m.0 as i128 * m.0 as i128) as u128)
+                    .sum();
+                <$t>::try_from(crate::area::isqrt(sum))
+                    .map(FixedDecimal)
+                    .expect("Value out of range")
+            }
+
+            pub const fn to_i128(self) -> i128 {
+                self.0 as i128
+            }
+
+            /// The raw count as little-endian bytes, for wire protocols and
+            /// stored tables.
+            pub const fn to_le_bytes(self) -> [u8; core::mem::size_of::<$t>()] {
+                self.0.to_le_bytes()
+            }
+
+            pub const fn to_be_bytes(self) -> [u8; core::mem::size_of::<$t>()] {
+                self.0.to_be_bytes()
+            }
+
+            pub const fn from_le_bytes(bytes: [u8; core::mem::size_of::<$t>()]) -> Self {
+                FixedDecimal(<$t>::from_le_bytes(bytes))
+            }
+
+            pub const fn from_be_bytes(bytes: [u8; core::mem::size_of::<$t>()]) -> Self {
+                FixedDecimal(<$t>::from_be_bytes(bytes))
+            }
+
+            /// Non-panicking `self + rhs` for floats from untrusted input.
+            pub fn try_add_f32(self, rhs: f32) -> Result<Self, ConversionError> {
+                let rhs = Self::try_from_f32(rhs)?;
+                exact::narrow(self.0 as i128 + rhs.0 as i128)
+            }
+
+            pub fn try_sub_f32(self, rhs: f32) -> Result<Self, ConversionError> {
+                let rhs = Self::try_from_f32(rhs)?;
+                exact::narrow(self.0 as i128 - rhs.0 as i128)
+            }
+
+            pub fn try_mul_f32(self, rhs: f32) -> Result<Self, ConversionError> {
+                exact::mul(self.0 as i128, rhs, RoundingMode::Trunc).and_then(exact::narrow)
+            }
+
+            /// Division by zero reports `Overflow` or `Underflow` by sign, or `NaN`
+            /// for zero divided by zero, like the float result would be.
+            pub fn try_div_f32(self, rhs: f32) -> Result<Self, ConversionError> {
+                exact::div(self.0 as i128, rhs, RoundingMode::Trunc).and_then(exact::narrow)
+            }
+
+            /// Computes `self * num / den` exactly, truncating toward zero.
+            /// Panics if `den` is zero or the result is out of range.
+            pub fn mul_ratio(self, num: i64, den: i64) -> Self {
+                self.mul_ratio_with(num, den, RoundingMode::Trunc)
+            }
+
+            /// Computes `self * num / den` exactly, rounding once with `mode`.
+            /// Panics if `den` is zero or the result is out of range.
+            pub fn mul_ratio_with(self, num: i64, den: i64, mode: RoundingMode) -> Self {
+                assert!(den != 0, "attempt to divide by zero");
+                let val = mode.div_i128(self.0 as i128 * num as i128, den as i128);
+                exact::narrow(val).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            /// Computes `self * factor + addend` with a single truncation toward
+            /// zero at the end, instead of one for each operation.
+            pub fn mul_add(self, factor: f32, addend: Self) -> Self {
+                exact::mul_add(
+                    self.0 as i128,
+                    factor,
+                    addend.0 as i128,
+                    RoundingMode::Trunc,
+                )
+                .and_then(exact::narrow)
+                .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            /// Snaps `self` to a multiple of `step`, picking between the neighboring
+            /// multiples with `mode`. Panics if `step` is zero or the snapped
+            /// value is out of range.
+            pub fn round_to(self, step: Self, mode: RoundingMode) -> Self {
+                assert!(step.0 != 0, "attempt to round to a zero step");
+                let step = (step.0 as i128).abs();
+                let val = mode.div_i128(self.0 as i128, step) * step;
+                exact::narrow(val).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            /// The largest multiple of `step` that is `<= self`.
+            pub fn floor_to(self, step: Self) -> Self {
+                self.round_to(step, RoundingMode::Floor)
+            }
+
+            /// The smallest multiple of `step` that is `>= self`.
+            pub fn ceil_to(self, step: Self) -> Self {
+                self.round_to(step, RoundingMode::Ceil)
+            }
+
+            /// The largest pitch that both `self` and `other` are multiples of,
+            /// always non-negative. Panics if that is `-MIN`, which only happens
+            /// for `MIN` paired with itself or zero.
+            pub fn gcd(self, other: Self) -> Self {
+                let (mut a, mut b) = (self.0.unsigned_abs(), other.0.unsigned_abs());
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                <$t>::try_from(a)
+                    .map(FixedDecimal)
+                    .expect("Value out of range")
+            }
+
+            /// The smallest non-negative pitch that is a multiple of both `self`
+            /// and `other`; zero if either is zero. Panics if it is out of range.
+            pub fn lcm(self, other: Self) -> Self {
+                if self.0 == 0 || other.0 == 0 {
+                    return Self::ZERO;
+                }
+                let gcd = self.gcd(other).0 as i128;
+                let val = (self.0 as i128 / gcd * other.0 as i128).abs();
+                exact::narrow(val).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            /// Sums in a wider integer so intermediate totals may leave the range;
+            /// returns `None` only if the final total doesn't fit.
+            pub fn checked_sum<I: IntoIterator<Item = Self>>(iter: I) -> Option<Self> {
+                let total = iter
+                    .into_iter()
+                    .try_fold(0 as $wide, |acc, m| acc.checked_add(m.0 as $wide))?;
+                <$t>::try_from(total).ok().map(FixedDecimal)
+            }
+
+            /// Truncates a float count of steps, checking the range first.
+            ///
+            /// A count within a few ulps of a whole step is taken as that
+            /// step: scaling millimeters to steps rounds, so `1.001 * 1000.0`
+            /// is just below 1001, and truncating that error away would
+            /// mean `f64::from(m)` doesn't convert back to `m`.
+            pub(crate) fn try_from_steps_f64(steps: f64) -> Result<Self, ConversionError> {
+                let nearest = float::round_f64(steps);
+                if (steps - nearest).abs() <= nearest.abs() * SNAP_ULPS * f64::EPSILON {
+                    Self::try_trunc_steps_f64(nearest)
+                } else {
+                    Self::try_trunc_steps_f64(steps)
+                }
+            }
+
+            /// Like `try_from_steps_f64`, but truncates even a count just
+            /// below a whole step.
+            pub(crate) fn try_trunc_steps_f64(steps: f64) -> Result<Self, ConversionError> {
+                if steps.is_nan() {
+                    return Err(ConversionError::NaN);
+                }
+                let steps = float::trunc_f64(steps);
+                // `MIN` is a power of two, so both bounds are exact
+                if steps >= -(<$t>::MIN as f64) {
+                    return Err(ConversionError::Overflow);
+                }
+                if steps < <$t>::MIN as f64 {
+                    return Err(ConversionError::Underflow);
+                }
+                Ok(FixedDecimal(steps as $t))
+            }
+
+            /// Non-panicking version of `From<f32>`.
+            ///
+            /// `TryFrom<f32>` can't be implemented alongside `From<f32>`, so
+            /// this is the fallible entry point for untrusted input.
+            pub fn try_from_f32(val: f32) -> Result<Self, ConversionError> {
+                Self::try_from_f32_with(val, RoundingMode::Trunc)
+            }
+
+            /// Like `From<f32>`, but clamps out of range values to
+            /// `MIN`/`MAX` and maps NaN to `ZERO`.
+            pub fn saturating_from_f32(val: f32) -> Self {
+                match Self::try_from_f32(val) {
+                    Ok(m) => m,
+                    Err(ConversionError::NaN) => Self::ZERO,
+                    Err(ConversionError::Overflow) => Self::MAX,
+                    Err(ConversionError::Underflow) => Self::MIN,
+                }
+            }
+
+            /// Converts millimeters to the nearest step, ties to even, so
+            /// that `0.9999999` becomes `Microns(1000)` rather than
+            /// `Microns(999)`.
+            pub fn from_f32_rounded(val: f32) -> Self {
+                Self::from_f32_with(val, RoundingMode::HalfEven)
+            }
+
+            /// Converts millimeters, rounding remainders smaller than a step
+            /// with `mode`.
+            ///
+            /// Every mode is monotonic: if `a <= b` then
+            /// `from_f32_with(a, mode) <= from_f32_with(b, mode)`.
+            pub fn from_f32_with(val: f32, mode: RoundingMode) -> Self {
+                Self::try_from_f32_with(val, mode)
+                    .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            pub fn try_from_f32_with(
+                val: f32,
+                mode: RoundingMode,
+            ) -> Result<Self, ConversionError> {
+                if val.is_nan() {
+                    return Err(ConversionError::NaN);
+                }
+                if val >= f32::from(Self::MAX) {
+                    return Err(ConversionError::Overflow);
+                }
+                if val <= f32::from(Self::MIN) {
+                    return Err(ConversionError::Underflow);
+                }
+                Ok(FixedDecimal(mode.round_f32(val * Self::PER_MM) as $t))
+            }
+        }
+
+        /// Converts millimeters, truncating toward zero.
+        ///
+        /// The conversion is monotonic: if `a <= b` then `from(a) <= from(b)`.
+        impl<const SCALE: u32> From<f32> for FixedDecimal<SCALE, $t> {
+            fn from(other: f32) -> Self {
+                Self::try_from_f32(other).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+        }
+
+        impl<const SCALE: u32> From<FixedDecimal<SCALE, $t>> for f32 {
+            fn from(other: FixedDecimal<SCALE, $t>) -> Self {
+                other.0 as f32 / FixedDecimal::<SCALE, $t>::PER_MM
+            }
+        }
+
+        impl<const SCALE: u32> From<FixedDecimal<SCALE, $t>> for f64 {
+            fn from(other: FixedDecimal<SCALE, $t>) -> Self {
+                other.0 as f64 / FixedDecimal::<SCALE, $t>::PER_MM as f64
+            }
+        }
+
+        /// The millimeter value in scientific notation, e.g. `1.2345e1`, via
+        /// `f64`. All flags, including precision, behave as for `f64`.
+        impl<const SCALE: u32> fmt::LowerExp for FixedDecimal<SCALE, $t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::LowerExp::fmt(&f64::from(*self), f)
+            }
+        }
+
+        impl<const SCALE: u32> fmt::UpperExp for FixedDecimal<SCALE, $t> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::UpperExp::fmt(&f64::from(*self), f)
+            }
+        }
+
+        /// Converts millimeters, truncating toward zero. Values within a few
+        /// ulps of a whole step are taken as that step, so that
+        /// `f64::from(m)` always converts back to `m`.
+        impl<const SCALE: u32> TryFrom<f64> for FixedDecimal<SCALE, $t> {
+            type Error = ConversionError;
+            fn try_from(other: f64) -> Result<Self, ConversionError> {
+                Self::try_from_steps_f64(other * Self::PER_MM as f64)
+            }
+        }
+
+        impl<const SCALE: u32> Add for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                FixedDecimal::<SCALE, $t>::add(self, rhs)
+            }
+        }
+
+        impl<const SCALE: u32> Add<f32> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn add(self, rhs: f32) -> Self {
+                self + Self::from(rhs)
+            }
+        }
+
+        impl<const SCALE: u32> Sub for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                FixedDecimal::<SCALE, $t>::sub(self, rhs)
+            }
+        }
+
+        impl<const SCALE: u32> Sub<f32> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn sub(self, rhs: f32) -> Self {
+                self - Self::from(rhs)
+            }
+        }
+
+        impl<const SCALE: u32> Rem for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn rem(self, rhs: Self) -> Self {
+                FixedDecimal(self.0 % rhs.0)
+            }
+        }
+
+        /// Overflows for `MIN`; see `checked_neg`.
+        impl<const SCALE: u32> Neg for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                FixedDecimal(-self.0)
+            }
+        }
+
+        /// Computed exactly in integer math and truncated toward zero, so
+        /// `Microns(-3) * 0.5 == Microns(-1)` at any magnitude.
+        impl<const SCALE: u32> Mul<f32> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                exact::mul(self.0 as i128, rhs, RoundingMode::Trunc)
+                    .and_then(exact::narrow)
+                    .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+        }
+
+        /// Computed exactly in integer math and truncated toward zero.
+        impl<const SCALE: u32> Div<f32> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn div(self, rhs: f32) -> Self {
+                exact::div(self.0 as i128, rhs, RoundingMode::Trunc)
+                    .and_then(exact::narrow)
+                    .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+        }
+
+        impl<const SCALE: u32> Mul<$t> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn mul(self, rhs: $t) -> Self {
+                FixedDecimal(self.0 * rhs)
+            }
+        }
+
+        impl<const SCALE: u32> Mul<FixedDecimal<SCALE, $t>> for $t {
+            type Output = FixedDecimal<SCALE, $t>;
+            fn mul(self, rhs: FixedDecimal<SCALE, $t>) -> FixedDecimal<SCALE, $t> {
+                rhs * self
+            }
+        }
+
+        /// Truncates toward zero like integer division.
+        impl<const SCALE: u32> Div<$t> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn div(self, rhs: $t) -> Self {
+                FixedDecimal(self.0 / rhs)
+            }
+        }
+
+        /// The dimensionless ratio of two lengths. Division by zero follows
+        /// float semantics; use `div_rem` for exact whole-step counts.
+        impl<const SCALE: u32> Div for FixedDecimal<SCALE, $t> {
+            type Output = f64;
+            fn div(self, rhs: Self) -> f64 {
+                self.0 as f64 / rhs.0 as f64
+            }
+        }
+
+        impl<const SCALE: u32> AddAssign for FixedDecimal<SCALE, $t> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const SCALE: u32> AddAssign<f32> for FixedDecimal<SCALE, $t> {
+            fn add_assign(&mut self, rhs: f32) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const SCALE: u32> SubAssign for FixedDecimal<SCALE, $t> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const SCALE: u32> SubAssign<f32> for FixedDecimal<SCALE, $t> {
+            fn sub_assign(&mut self, rhs: f32) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const SCALE: u32> MulAssign<f32> for FixedDecimal<SCALE, $t> {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const SCALE: u32> MulAssign<$t> for FixedDecimal<SCALE, $t> {
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const SCALE: u32> DivAssign<f32> for FixedDecimal<SCALE, $t> {
+            fn div_assign(&mut self, rhs: f32) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<const SCALE: u32> DivAssign<$t> for FixedDecimal<SCALE, $t> {
+            fn div_assign(&mut self, rhs: $t) {
+                *self = *self / rhs;
+            }
+        }
+
+        /// Panics if the total doesn't fit; see `checked_sum`.
+        impl<const SCALE: u32> Sum for FixedDecimal<SCALE, $t> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                Self::checked_sum(iter).expect("attempt to sum with overflow")
+            }
+        }
+
+        impl<'a, const SCALE: u32> Sum<&'a FixedDecimal<SCALE, $t>> for FixedDecimal<SCALE, $t> {
+            fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+                iter.copied().sum()
+            }
+        }
+
+        impl<const SCALE: u32> From<FixedDecimal<SCALE, $t>> for Checked<FixedDecimal<SCALE, $t>> {
+            fn from(other: FixedDecimal<SCALE, $t>) -> Self {
+                Checked(other)
+            }
+        }
+
+        impl<const SCALE: u32> Add for Checked<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            #[track_caller]
+            fn add(self, rhs: Self) -> Self {
+                Checked(self.0.strict_add(rhs.0))
+            }
+        }
+
+        impl<const SCALE: u32> Sub for Checked<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            #[track_caller]
+            fn sub(self, rhs: Self) -> Self {
+                Checked(self.0.strict_sub(rhs.0))
+            }
+        }
+
+        impl<const SCALE: u32> Mul<$t> for Checked<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            #[track_caller]
+            fn mul(self, rhs: $t) -> Self {
+                let val = self.0 .0.checked_mul(rhs);
+                Checked(FixedDecimal(
+                    val.expect("attempt to multiply with overflow"),
+                ))
+            }
+        }
+
+        impl<const SCALE: u32> Neg for Checked<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            #[track_caller]
+            fn neg(self) -> Self {
+                Checked(self.0.strict_neg())
+            }
+        }
+
+        impl<const SCALE: u32> AddAssign for Checked<FixedDecimal<SCALE, $t>> {
+            #[track_caller]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const SCALE: u32> SubAssign for Checked<FixedDecimal<SCALE, $t>> {
+            #[track_caller]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const SCALE: u32> MulAssign<$t> for Checked<FixedDecimal<SCALE, $t>> {
+            #[track_caller]
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<const SCALE: u32> From<FixedDecimal<SCALE, $t>>
+            for Saturating<FixedDecimal<SCALE, $t>>
+        {
+            fn from(other: FixedDecimal<SCALE, $t>) -> Self {
+                Saturating(other)
+            }
+        }
+
+        impl<const SCALE: u32> Add for Saturating<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                Saturating(self.0.saturating_add(rhs.0))
+            }
+        }
+
+        impl<const SCALE: u32> Sub for Saturating<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                Saturating(self.0.saturating_sub(rhs.0))
+            }
+        }
+
+        impl<const SCALE: u32> Mul<$t> for Saturating<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            fn mul(self, rhs: $t) -> Self {
+                Saturating(FixedDecimal(self.0 .0.saturating_mul(rhs)))
+            }
+        }
+
+        impl<const SCALE: u32> Neg for Saturating<FixedDecimal<SCALE, $t>> {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Saturating(FixedDecimal(self.0 .0.saturating_neg()))
+            }
+        }
+
+        impl<const SCALE: u32> AddAssign for Saturating<FixedDecimal<SCALE, $t>> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<const SCALE: u32> SubAssign for Saturating<FixedDecimal<SCALE, $t>> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<const SCALE: u32> MulAssign<$t> for Saturating<FixedDecimal<SCALE, $t>> {
+            fn mul_assign(&mut self, rhs: $t) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+impl_fixed!(i32, i64);
+impl_fixed!(i64, i128);
+
+/// Widening to a 64-bit backing is lossless.
+impl<const SCALE: u32> From<FixedDecimal<SCALE, i32>> for FixedDecimal<SCALE, i64> {
+    fn from(other: FixedDecimal<SCALE, i32>) -> Self {
+        FixedDecimal(other.0 as i64)
+    }
+}
+
+/// Fails with `Overflow` or `Underflow` outside the 32-bit range.
+impl<const SCALE: u32> TryFrom<FixedDecimal<SCALE, i64>> for FixedDecimal<SCALE, i32> {
+    type Error = ConversionError;
+    fn try_from(other: FixedDecimal<SCALE, i64>) -> Result<Self, ConversionError> {
+        exact::narrow(other.0 as i128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Millis = FixedDecimal<0, i32>;
+    type TenthMicrons = FixedDecimal<4, i32>;
+
+    #[test]
+    fn test_other_scales() {
+        assert_eq!(Millis::MM, FixedDecimal(1));
+        assert_eq!(TenthMicrons::MM, FixedDecimal(10_000));
+        assert_eq!(Millis::from(2.9f32), FixedDecimal(2));
+        assert_eq!(TenthMicrons::from(-0.00015f32), FixedDecimal(-1));
+        assert_eq!(TenthMicrons::try_from(1.5f64), Ok(FixedDecimal(15_000)));
+        assert_eq!(
+            TenthMicrons::try_from(300_000.0f64),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(f64::from(TenthMicrons::new(12_345)), 1.2345);
+        assert_eq!(Millis::new(5) * 0.5, Millis::new(2));
+        assert_eq!(format!("{:?}", Millis::new(5)), "FixedDecimal(5 = 5mm)");
+        assert_eq!(
+            [Millis::MAX, Millis::new(1), Millis::new(-1)]
+                .iter()
+                .sum::<Millis>(),
+            Millis::MAX
+        );
+    }
+
+    #[test]
+    fn test_widths() {
+        use crate::{Microns, Microns64};
+        let far = Microns64::from_m(5_000.0);
+        assert_eq!(far.0, 5_000_000_000);
+        assert_eq!(Microns::try_from(far), Err(ConversionError::Overflow));
+        assert_eq!(Microns::try_from(-far), Err(ConversionError::Underflow));
+        assert_eq!(Microns64::from(Microns::MIN).0, i32::MIN as i64);
+        assert_eq!(
+            Microns::try_from(Microns64::from(Microns(-7))),
+            Ok(Microns(-7))
+        );
+        // the same API on both widths
+        let step = Microns64::MM * 3;
+        assert_eq!(
+            (far + step).round_to(Microns64::M, RoundingMode::HalfUp),
+            far
+        );
+        assert_eq!(far.lerp(-far, 0.5), Microns64::ZERO);
+        assert_eq!(step.hypot(Microns64::MM * 4), Microns64::MM * 5);
+        assert_eq!(
+            Microns64::try_from(1e13f64),
+            Ok(FixedDecimal(10_000_000_000_000_000))
+        );
+        assert_eq!(Microns64::from(2.5f32), FixedDecimal(2_500));
+        assert_eq!(
+            format!("{:?}", Microns64::INCH),
+            "Microns(25400 = 25.400mm)"
+        );
+        assert_eq!([far, far].iter().sum::<Microns64>(), far * 2);
+    }
+
+    #[test]
+    fn test_byte_encoding() {
+        use crate::{Microns, Microns64};
+        assert_eq!(Microns(0x0102_0304).to_le_bytes(), [4, 3, 2, 1]);
+        assert_eq!(Microns(0x0102_0304).to_be_bytes(), [1, 2, 3, 4]);
+        assert_eq!(Microns::from_le_bytes([0xff; 4]), Microns(-1));
+        assert_eq!(Microns::from_be_bytes([0x80, 0, 0, 0]), Microns::MIN);
+        let m = Microns64::new(-123_456_789_012);
+        assert_eq!(Microns64::from_be_bytes(m.to_be_bytes()), m);
+        assert_eq!(Microns64::from_le_bytes(m.to_le_bytes()), m);
+        assert_eq!(m.to_le_bytes().len(), 8);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_bytemuck() {
+        use crate::Microns;
+        let path = [Microns(-1), Microns(256)];
+        assert_eq!(bytemuck::cast_slice::<_, i32>(&path), &[-1, 256]);
+        let bytes: &[u8] = bytemuck::cast_slice(&path);
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(
+            bytemuck::pod_read_unaligned::<Microns>(&bytes[4..]),
+            Microns(256)
+        );
+        assert_eq!(bytemuck::cast_slice::<i32, Microns>(&[5]), &[Microns(5)]);
+        assert_eq!(<Microns as bytemuck::Zeroable>::zeroed(), Microns::ZERO);
+    }
+
+    #[cfg(feature = "zerocopy")]
+    #[test]
+    fn test_zerocopy() {
+        use crate::Microns;
+        use zerocopy::{FromBytes, IntoBytes};
+
+        #[derive(FromBytes, IntoBytes, zerocopy::Immutable, zerocopy::KnownLayout)]
+        #[repr(C)]
+        struct Position {
+            x: Microns,
+            y: Microns,
+        }
+        let packet = Position {
+            x: Microns(-1),
+            y: Microns(1000),
+        };
+        let bytes = packet.as_bytes();
+        assert_eq!(&bytes[4..], 1000i32.to_ne_bytes());
+        let read = Position::read_from_bytes(bytes).unwrap();
+        assert_eq!((read.x, read.y), (Microns(-1), Microns(1000)));
+        assert_eq!(
+            <[Microns]>::ref_from_bytes(&bytes[..4]).unwrap(),
+            &[Microns(-1)]
+        );
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv() {
+        use crate::Microns;
+        let path = vec![Microns(-1), Microns::MAX, Microns(12_345)];
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&path).unwrap();
+        let archived =
+            rkyv::access::<rkyv::Archived<Vec<Microns>>, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[1], Microns::MAX);
+        assert_eq!(archived[2].to_native(), Microns(12_345));
+        let back: Vec<Microns> = rkyv::deserialize::<_, rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(back, path);
+    }
+
+    #[test]
+    fn test_display() {
+        use crate::{Microns, Microns64};
+        assert_eq!(Microns(12_345).to_string(), "12.345");
+        assert_eq!(Microns(-5).to_string(), "-0.005");
+        assert_eq!(Microns::ZERO.to_string(), "0.000");
+        assert_eq!(format!("{:?}", Microns(-5)), "Microns(-5 = -0.005mm)");
+        assert_eq!(format!("{:e}", Microns(12_345)), "1.2345e1");
+        assert_eq!(format!("{:.2E}", Microns(-250)), "-2.50E-1");
+        assert_eq!(format!("{:+e}", Microns64::M), "+1e3");
+        assert_eq!(format!("{:e}", TenthMicrons::new(5)), "5e-4");
+        assert_eq!(
+            format!("{:?}", TenthMicrons::new(12)),
+            "FixedDecimal(12 = 0.0012mm)"
+        );
+        assert_eq!(Microns::MIN.to_string(), "-2147483.648");
+        assert_eq!(Microns64::MAX.to_string(), "9223372036854775.807");
+        assert_eq!(Millis::new(-42).to_string(), "-42");
+        assert_eq!(TenthMicrons::new(5).to_string(), "0.0005");
+        assert_eq!(
+            format!("{:>8}|{:+}|{:08}", Microns(1500), Microns(1), Microns(-1)),
+            "   1.500|+0.001|-000.001"
+        );
+    }
+}
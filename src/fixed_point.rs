@@ -0,0 +1,87 @@
+//! Conversions to and from the `fixed` crate's binary fixed-point types,
+//! with values in millimeters.
+//!
+//! A micrometer isn't a whole number of binary fractions, so both
+//! directions round to nearest, ties away from zero. The binary types have
+//! resolution to spare (2⁻³² mm and 2⁻¹⁶ mm), so every Microns they can hold
+//! comes back unchanged.
+use crate::{ConversionError, Microns, RoundingMode};
+use ::fixed::types::{I16F16, I32F32};
+
+/// Exact up to rounding; every Microns fits.
+impl From<Microns> for I32F32 {
+    fn from(m: Microns) -> Self {
+        let bits = RoundingMode::HalfUp.div_i128((m.0 as i128) << 32, 1000);
+        I32F32::from_bits(bits as i64)
+    }
+}
+
+/// Fails for values beyond `Microns::MIN..=Microns::MAX`.
+impl TryFrom<I32F32> for Microns {
+    type Error = ConversionError;
+    fn try_from(mm: I32F32) -> Result<Self, ConversionError> {
+        let um = RoundingMode::HalfUp.div_i128(mm.to_bits() as i128 * 1000, 1 << 32);
+        crate::exact::narrow::<3, i32>(um)
+    }
+}
+
+/// Fails outside ±32768 mm, the range of `I16F16`.
+impl TryFrom<Microns> for I16F16 {
+    type Error = ConversionError;
+    fn try_from(m: Microns) -> Result<Self, ConversionError> {
+        let bits = RoundingMode::HalfUp.div_i128((m.0 as i128) << 16, 1000);
+        i32::try_from(bits).map(I16F16::from_bits).map_err(|_| {
+            if bits < 0 {
+                ConversionError::Underflow
+            } else {
+                ConversionError::Overflow
+            }
+        })
+    }
+}
+
+/// Every `I16F16` fits.
+impl From<I16F16> for Microns {
+    fn from(mm: I16F16) -> Self {
+        Microns(RoundingMode::HalfUp.div_i128(mm.to_bits() as i128 * 1000, 1 << 16) as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_conversions() {
+        for m in [Microns(1), Microns(-1250), Microns::MIN, Microns::MAX] {
+            assert_eq!(Microns::try_from(I32F32::from(m)), Ok(m));
+        }
+        assert_eq!(I32F32::from(Microns(1500)), I32F32::from_num(1.5));
+        assert_eq!(
+            Microns::try_from(I32F32::MAX),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Microns::try_from(I32F32::MIN),
+            Err(ConversionError::Underflow)
+        );
+        for m in [
+            Microns(1),
+            Microns(-7),
+            Microns(32_767_999),
+            Microns(-32_768_000),
+        ] {
+            assert_eq!(Microns::from(I16F16::try_from(m).unwrap()), m);
+        }
+        assert_eq!(I16F16::try_from(Microns(-2500)), Ok(I16F16::from_num(-2.5)));
+        assert_eq!(
+            I16F16::try_from(Microns(32_768_000)),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            I16F16::try_from(Microns::MIN),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(Microns::from(I16F16::MAX), Microns(32_768_000));
+    }
+}
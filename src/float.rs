@@ -0,0 +1,115 @@
+//! Float rounding that doesn't need std, which only gets it from the
+//! platform's libm.
+//!
+//! Below 2^23 for f32 and 2^52 for f64 a float's integer part fits a cast
+//! exactly; above, every float is already an integer.
+
+pub(crate) fn trunc(x: f32) -> f32 {
+    if x.abs() < 8_388_608.0 {
+        (x as i32 as f32).copysign(x)
+    } else {
+        x
+    }
+}
+
+pub(crate) fn trunc_f64(x: f64) -> f64 {
+    if x.abs() < 4_503_599_627_370_496.0 {
+        (x as i64 as f64).copysign(x)
+    } else {
+        x
+    }
+}
+
+pub(crate) fn floor(x: f32) -> f32 {
+    let t = trunc(x);
+    if t > x {
+        t - 1.0
+    } else {
+        t
+    }
+}
+
+pub(crate) fn ceil(x: f32) -> f32 {
+    let t = trunc(x);
+    if t < x {
+        t + 1.0
+    } else {
+        t
+    }
+}
+
+/// To nearest, ties away from zero.
+pub(crate) fn round(x: f32) -> f32 {
+    let t = trunc(x);
+    // exact, since `t` and `x` share their integer part
+    if (x - t).abs() >= 0.5 {
+        t + x.signum()
+    } else {
+        t
+    }
+}
+
+/// Like `round`, for f64.
+pub(crate) fn round_f64(x: f64) -> f64 {
+    let t = trunc_f64(x);
+    if (x - t).abs() >= 0.5 {
+        t + x.signum()
+    } else {
+        t
+    }
+}
+
+/// To nearest, ties to even.
+pub(crate) fn round_ties_even(x: f32) -> f32 {
+    let t = trunc(x);
+    let frac = (x - t).abs();
+    if frac > 0.5 || (frac == 0.5 && t % 2.0 != 0.0) {
+        t + x.signum()
+    } else {
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_std() {
+        let cases = [
+            0.0,
+            -0.0,
+            0.5,
+            -0.5,
+            1.5,
+            -1.5,
+            2.5,
+            -2.5,
+            0.49999997,
+            2.7,
+            -2.7,
+            8_388_607.5,
+            -8_388_607.5,
+            16_777_216.0,
+            1e30,
+            -1e30,
+        ];
+        for x in cases {
+            assert_eq!(trunc(x).to_bits(), x.trunc().to_bits(), "trunc {x}");
+            assert_eq!(floor(x).to_bits(), x.floor().to_bits(), "floor {x}");
+            assert_eq!(ceil(x).to_bits(), x.ceil().to_bits(), "ceil {x}");
+            assert_eq!(round(x).to_bits(), x.round().to_bits(), "round {x}");
+            let even = round_ties_even(x).to_bits();
+            assert_eq!(even, x.round_ties_even().to_bits(), "round_ties_even {x}");
+            let y = x as f64 * 3.3;
+            assert_eq!(trunc_f64(y).to_bits(), y.trunc().to_bits(), "trunc_f64 {y}");
+            assert_eq!(round_f64(y).to_bits(), y.round().to_bits(), "round_f64 {y}");
+        }
+        for f in [trunc, floor, ceil, round, round_ties_even] {
+            assert!(f(f32::NAN).is_nan());
+            assert_eq!(f(f32::NEG_INFINITY), f32::NEG_INFINITY);
+        }
+        assert!(trunc_f64(f64::NAN).is_nan());
+        assert!(round_f64(f64::NAN).is_nan());
+    }
+}
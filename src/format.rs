@@ -0,0 +1,215 @@
+//! Configurable formatting: unit, decimal places, zero trimming and sign.
+//!
+//! ```
+//! use microns::{Microns, Unit};
+//!
+//! let x = Microns(12_500);
+//! assert_eq!(x.format().to_string(), "12.500");
+//! assert_eq!(x.format().trim_zeros(true).plus_sign(true).to_string(), "+12.5");
+//! assert_eq!(x.format().unit(Unit::Um).suffix(true).to_string(), "12500µm");
+//! assert_eq!(x.format().unit(Unit::Inch).decimals(4).to_string(), "0.4921");
+//! ```
+use crate::fixed::decimal_digits;
+use crate::{FixedDecimal, RoundingMode};
+use core::fmt;
+
+/// The unit a length is written in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    #[default]
+    Mm,
+    Um,
+    Inch,
+}
+
+impl Unit {
+    /// Micrometers per unit.
+    const fn um(self) -> i128 {
+        match self {
+            Unit::Mm => 1000,
+            Unit::Um => 1,
+            Unit::Inch => 25_400,
+        }
+    }
+
+    /// The natural number of decimals: enough to show every micrometer in
+    /// mm, none in µm, and a tenth of a thou in inches.
+    const fn default_decimals(self) -> u32 {
+        match self {
+            Unit::Mm => 3,
+            Unit::Um => 0,
+            Unit::Inch => 4,
+        }
+    }
+
+    pub const fn suffix(self) -> &'static str {
+        match self {
+            Unit::Mm => "mm",
+            Unit::Um => "µm",
+            Unit::Inch => "in",
+        }
+    }
+}
+
+/// A length with formatting options, built by `Microns::format` and
+/// printed with `Display`. Width and fill from the format string apply to
+/// the whole result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Format {
+    um: i128,
+    unit: Unit,
+    decimals: Option<u32>,
+    rounding: RoundingMode,
+    trim_zeros: bool,
+    plus_sign: bool,
+    suffix: bool,
+}
+
+impl Format {
+    /// Defaults to millimeters with three decimals.
+    pub const fn new(um: i128) -> Self {
+        Format {
+            um,
+            unit: Unit::Mm,
+            decimals: None,
+            rounding: RoundingMode::HalfUp,
+            trim_zeros: false,
+            plus_sign: false,
+            suffix: false,
+        }
+    }
+
+    /// The output unit. Unless `decimals` is set, also picks the unit's
+    /// natural number of decimals: 3 for mm, 0 for µm and 4 for inches.
+    pub const fn unit(mut self, unit: Unit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Digits after the decimal point, at most 18. Panics above that.
+    pub const fn decimals(mut self, decimals: u32) -> Self {
+        assert!(decimals <= 18, "at most 18 decimal places");
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// How to round digits past `decimals`; ties away from zero by default.
+    pub const fn rounding(mut self, mode: RoundingMode) -> Self {
+        self.rounding = mode;
+        self
+    }
+
+    /// Drops trailing zeros after the point, and the point if nothing is
+    /// left: `12.500` becomes `12.5` and `3.000` becomes `3`.
+    pub const fn trim_zeros(mut self, trim: bool) -> Self {
+        self.trim_zeros = trim;
+        self
+    }
+
+    /// Writes `+` before positive values and zero.
+    pub const fn plus_sign(mut self, plus: bool) -> Self {
+        self.plus_sign = plus;
+        self
+    }
+
+    /// Appends the unit, e.g. `12.5mm`.
+    pub const fn suffix(mut self, suffix: bool) -> Self {
+        self.suffix = suffix;
+        self
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = match self.decimals {
+            Some(decimals) => decimals,
+            None => self.unit.default_decimals(),
+        };
+        let scaled = self
+            .rounding
+            .div_i128(self.um * 10i128.pow(decimals), self.unit.um());
+        let mut digits = [0; 80];
+        let mut text = decimal_digits(scaled.unsigned_abs(), decimals, &mut digits);
+        if self.trim_zeros && decimals > 0 {
+            text = text.trim_end_matches('0').trim_end_matches('.');
+        }
+        let sign = if scaled < 0 {
+            "-"
+        } else if self.plus_sign {
+            "+"
+        } else {
+            ""
+        };
+        let suffix = if self.suffix { self.unit.suffix() } else { "" };
+        let mut buf = [0; 96];
+        let mut len = 0;
+        for part in [sign, text, suffix] {
+            buf[len..len + part.len()].copy_from_slice(part.as_bytes());
+            len += part.len();
+        }
+        f.pad(core::str::from_utf8(&buf[..len]).expect("built from str parts"))
+    }
+}
+
+macro_rules! impl_format {
+    ($($t:ty),*) => {$(
+        impl FixedDecimal<3, $t> {
+            /// Starts a `Format` for `self`, by default the same as `Display`.
+            pub const fn format(self) -> Format {
+                Format::new(self.0 as i128)
+            }
+        }
+    )*};
+}
+impl_format!(i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Microns, Microns64};
+
+    #[test]
+    fn test_format_options() {
+        let x = Microns(-1_005);
+        assert_eq!(x.format().to_string(), x.to_string());
+        assert_eq!(x.format().decimals(2).to_string(), "-1.01");
+        assert_eq!(
+            x.format()
+                .decimals(2)
+                .rounding(RoundingMode::Trunc)
+                .to_string(),
+            "-1.00"
+        );
+        assert_eq!(x.format().decimals(0).to_string(), "-1");
+        assert_eq!(Microns(-4).format().decimals(2).to_string(), "0.00");
+        assert_eq!(Microns(3_000).format().trim_zeros(true).to_string(), "3");
+        assert_eq!(Microns::ZERO.format().plus_sign(true).to_string(), "+0.000");
+        assert_eq!(x.format().plus_sign(true).to_string(), "-1.005");
+        assert_eq!(x.format().unit(Unit::Um).to_string(), "-1005");
+        assert_eq!(x.format().unit(Unit::Um).decimals(1).to_string(), "-1005.0");
+        assert_eq!(
+            Microns::INCH
+                .format()
+                .unit(Unit::Inch)
+                .suffix(true)
+                .to_string(),
+            "1.0000in"
+        );
+        assert_eq!(
+            format!(
+                "{:>10}|{:<8}|",
+                Microns(1_500).format().suffix(true),
+                Microns(2).format()
+            ),
+            "   1.500mm|0.002   |"
+        );
+        assert_eq!(
+            Microns64::MAX
+                .format()
+                .unit(Unit::Mm)
+                .decimals(18)
+                .to_string(),
+            "9223372036854775.807000000000000000"
+        );
+    }
+}
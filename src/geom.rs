@@ -0,0 +1,481 @@
+//! Points and vectors with Microns coordinates.
+//!
+//! Points are positions and vectors are displacements: subtracting two
+//! points gives the vector between them, and a vector moves a point. Only
+//! vectors scale, since scaling a position depends on the origin.
+use crate::{ConversionError, Microns, SquareMicrons};
+use core::ops::{
+    Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+macro_rules! geom_struct {
+    ($(#[$doc:meta])* $name:ident { $($c:ident),* }) => {
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+        #[cfg_attr(
+            feature = "rkyv",
+            derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+            rkyv(compare(PartialEq))
+        )]
+        #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $(#[$doc])*
+        pub struct $name {
+            $(pub $c: Microns,)*
+        }
+
+        impl $name {
+            pub const fn new($($c: Microns),*) -> Self {
+                $name { $($c),* }
+            }
+        }
+
+        /// The coordinate along an axis, counting from x.
+        impl Index<usize> for $name {
+            type Output = Microns;
+            fn index(&self, axis: usize) -> &Microns {
+                let mut n = 0;
+                $(
+                    if axis == n {
+                        return &self.$c;
+                    }
+                    n += 1;
+                )*
+                panic!("axis {axis} out of range for {}", stringify!($name))
+            }
+        }
+
+        impl IndexMut<usize> for $name {
+            fn index_mut(&mut self, axis: usize) -> &mut Microns {
+                let mut n = 0;
+                $(
+                    if axis == n {
+                        return &mut self.$c;
+                    }
+                    n += 1;
+                )*
+                panic!("axis {axis} out of range for {}", stringify!($name))
+            }
+        }
+
+        /// Converts millimeters, truncating toward zero like
+        /// `Microns::from(f32)`.
+        impl From<($(geom_struct!(@float $c f32)),*)> for $name {
+            fn from(($($c),*): ($(geom_struct!(@float $c f32)),*)) -> Self {
+                $name { $($c: Microns::from($c)),* }
+            }
+        }
+
+        /// Converts millimeters, truncating toward zero.
+        impl TryFrom<($(geom_struct!(@float $c f64)),*)> for $name {
+            type Error = ConversionError;
+            fn try_from(
+                ($($c),*): ($(geom_struct!(@float $c f64)),*),
+            ) -> Result<Self, ConversionError> {
+                Ok($name { $($c: Microns::try_from($c)?),* })
+            }
+        }
+
+        /// Millimeters.
+        impl From<$name> for ($(geom_struct!(@float $c f32)),*) {
+            fn from(v: $name) -> Self {
+                ($(f32::from(v.$c)),*)
+            }
+        }
+
+        impl From<$name> for ($(geom_struct!(@float $c f64)),*) {
+            fn from(v: $name) -> Self {
+                ($(f64::from(v.$c)),*)
+            }
+        }
+    };
+    (@float $c:ident $f:ty) => { $f };
+}
+
+geom_struct!(
+    /// Point2 is a position in the plane.
+    Point2 { x, y }
+);
+geom_struct!(
+    /// Point3 is a position in space.
+    Point3 { x, y, z }
+);
+geom_struct!(
+    /// Vec2 is a displacement in the plane.
+    Vec2 { x, y }
+);
+geom_struct!(
+    /// Vec3 is a displacement in space.
+    Vec3 { x, y, z }
+);
+
+/// Point2 and Point3, for code that works in either dimension.
+pub trait MicronPoint: Copy {
+    /// The number of coordinates.
+    const DIM: usize;
+
+    /// The coordinate along `axis`, counting from x.
+    ///
+    /// # Panics
+    ///
+    /// If `axis >= DIM`.
+    fn coord(&self, axis: usize) -> Microns;
+
+    /// The exact squared distance to `other`, in square micrometers.
+    fn distance_sq(&self, other: &Self) -> i128;
+}
+
+macro_rules! impl_geom_ops {
+    ($point:ident, $vec:ident { $($c:ident),* }) => {
+        impl $point {
+            pub const ORIGIN: $point = $point { $($c: Microns::ZERO),* };
+        }
+
+        impl $vec {
+            pub const ZERO: $vec = $vec { $($c: Microns::ZERO),* };
+
+            /// The exact dot product, in square micrometers.
+            pub const fn dot_i128(self, rhs: Self) -> i128 {
+                0 $(+ self.$c.0 as i128 * rhs.$c.0 as i128)*
+            }
+
+            /// The dot product as an area.
+            ///
+            /// # Panics
+            ///
+            /// If it doesn't fit in an `i64`, which takes components near
+            /// the limits of Microns; `dot_i128` never overflows.
+            pub fn dot(self, rhs: Self) -> SquareMicrons {
+                self.checked_dot(rhs).expect("Value out of range")
+            }
+
+            /// Returns `None` if the dot product doesn't fit in an `i64`.
+            pub fn checked_dot(self, rhs: Self) -> Option<SquareMicrons> {
+                i64::try_from(self.dot_i128(rhs)).ok().map(SquareMicrons)
+            }
+
+            /// The exact squared length, in square micrometers.
+            pub const fn norm_sq_i128(self) -> i128 {
+                self.dot_i128(self)
+            }
+
+            /// The length in millimeters, rounded only by the square root.
+            #[cfg(feature = "std")]
+            pub fn norm_mm(self) -> f64 {
+                (self.norm_sq_i128() as f64).sqrt() / 1000.0
+            }
+        }
+
+        impl MicronPoint for $point {
+            const DIM: usize = [$(stringify!($c)),*].len();
+
+            fn coord(&self, axis: usize) -> Microns {
+                [$(self.$c),*][axis]
+            }
+
+            fn distance_sq(&self, other: &Self) -> i128 {
+                // the difference can need 33 bits, more than a vector holds
+                0 $(+ (self.$c.0 as i128 - other.$c.0 as i128).pow(2))*
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $vec;
+            fn sub(self, rhs: Self) -> $vec {
+                $vec { $($c: self.$c - rhs.$c),* }
+            }
+        }
+
+        impl Add<$vec> for $point {
+            type Output = Self;
+            fn add(self, rhs: $vec) -> Self {
+                $point { $($c: self.$c + rhs.$c),* }
+            }
+        }
+
+        impl Sub<$vec> for $point {
+            type Output = Self;
+            fn sub(self, rhs: $vec) -> Self {
+                $point { $($c: self.$c - rhs.$c),* }
+            }
+        }
+
+        impl AddAssign<$vec> for $point {
+            fn add_assign(&mut self, rhs: $vec) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign<$vec> for $point {
+            fn sub_assign(&mut self, rhs: $vec) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl Add for $vec {
+            type Output = Self;
+            fn add(self, rhs: Self) -> Self {
+                $vec { $($c: self.$c + rhs.$c),* }
+            }
+        }
+
+        impl Sub for $vec {
+            type Output = Self;
+            fn sub(self, rhs: Self) -> Self {
+                $vec { $($c: self.$c - rhs.$c),* }
+            }
+        }
+
+        impl Neg for $vec {
+            type Output = Self;
+            fn neg(self) -> Self {
+                $vec { $($c: -self.$c),* }
+            }
+        }
+
+        impl Mul<i32> for $vec {
+            type Output = Self;
+            fn mul(self, rhs: i32) -> Self {
+                $vec { $($c: self.$c * rhs),* }
+            }
+        }
+
+        impl Mul<$vec> for i32 {
+            type Output = $vec;
+            fn mul(self, rhs: $vec) -> $vec {
+                rhs * self
+            }
+        }
+
+        /// Each component as `Microns * f32`, exact and truncated toward
+        /// zero.
+        impl Mul<f32> for $vec {
+            type Output = Self;
+            fn mul(self, rhs: f32) -> Self {
+                $vec { $($c: self.$c * rhs),* }
+            }
+        }
+
+        /// Truncates toward zero like integer division.
+        impl Div<i32> for $vec {
+            type Output = Self;
+            fn div(self, rhs: i32) -> Self {
+                $vec { $($c: self.$c / rhs),* }
+            }
+        }
+
+        impl Div<f32> for $vec {
+            type Output = Self;
+            fn div(self, rhs: f32) -> Self {
+                $vec { $($c: self.$c / rhs),* }
+            }
+        }
+
+        impl MulAssign<i32> for $vec {
+            fn mul_assign(&mut self, rhs: i32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl MulAssign<f32> for $vec {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign<i32> for $vec {
+            fn div_assign(&mut self, rhs: i32) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl DivAssign<f32> for $vec {
+            fn div_assign(&mut self, rhs: f32) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl AddAssign for $vec {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $vec {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+impl_geom_ops!(Point2, Vec2 { x, y });
+impl_geom_ops!(Point3, Vec3 { x, y, z });
+
+impl Vec2 {
+    /// The z component of the 3D cross product, as a signed area: positive
+    /// if `rhs` is counterclockwise of `self`. Each product is at most 2^62
+    /// in magnitude, so their difference always fits in an `i64`.
+    pub const fn cross(self, rhs: Self) -> SquareMicrons {
+        SquareMicrons(self.x.0 as i64 * rhs.y.0 as i64 - self.y.0 as i64 * rhs.x.0 as i64)
+    }
+}
+
+impl Vec3 {
+    /// The cross product's x, y and z as areas. Like `Vec2::cross`, each
+    /// always fits in an `i64`.
+    pub const fn cross(self, rhs: Self) -> [SquareMicrons; 3] {
+        let [ax, ay, az] = [self.x.0 as i64, self.y.0 as i64, self.z.0 as i64];
+        let [bx, by, bz] = [rhs.x.0 as i64, rhs.y.0 as i64, rhs.z.0 as i64];
+        [
+            SquareMicrons(ay * bz - az * by),
+            SquareMicrons(az * bx - ax * bz),
+            SquareMicrons(ax * by - ay * bx),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points_and_vectors() {
+        let a = Point3::new(Microns(1), Microns(2), Microns(3));
+        let b = Point3::new(Microns(4), Microns(6), Microns(8));
+        let v = b - a;
+        assert_eq!(v, Vec3::new(Microns(3), Microns(4), Microns(5)));
+        assert_eq!(a + v, b);
+        assert_eq!(b - v, a);
+        assert_eq!(v - v, Vec3::ZERO);
+        assert_eq!(-v + v, Vec3::ZERO);
+        let mut p = Point2::ORIGIN;
+        p += Vec2::new(Microns::MM, -Microns::MM);
+        p -= Vec2::new(Microns(1), Microns(1));
+        assert_eq!(p, Point2::new(Microns(999), Microns(-1001)));
+    }
+
+    #[test]
+    fn test_micron_point() {
+        let a = Point3::new(Microns::MIN, Microns(0), Microns(7));
+        let b = Point3::new(Microns::MAX, Microns(0), Microns(7));
+        assert_eq!(Point3::DIM, 3);
+        assert_eq!(a.coord(2), Microns(7));
+        assert_eq!(a.distance_sq(&b), (u32::MAX as i128).pow(2));
+        let p = Point2::new(Microns(3), Microns(-4));
+        assert_eq!((Point2::DIM, p.distance_sq(&Point2::ORIGIN)), (2, 25));
+    }
+
+    #[test]
+    fn test_scale_and_index() {
+        let v = Vec2::new(Microns(-3), Microns(10));
+        assert_eq!(v * 2, Vec2::new(Microns(-6), Microns(20)));
+        assert_eq!(2 * v, v * 2);
+        assert_eq!(v * 0.5, Vec2::new(Microns(-1), Microns(5)));
+        assert_eq!(v / 2, v * 0.5);
+        assert_eq!(v / 0.5, v * 2);
+        let mut w = Vec3::new(Microns(1), Microns(2), Microns(3));
+        w *= 3;
+        w /= 1.5;
+        assert_eq!(w, Vec3::new(Microns(2), Microns(4), Microns(6)));
+        w[2] += Microns(1);
+        assert_eq!((w[0], w[1], w[2]), (Microns(2), Microns(4), Microns(7)));
+        let mut p = Point2::ORIGIN;
+        p[1] = Microns::MM;
+        assert_eq!(p.y, Microns::MM);
+    }
+
+    #[test]
+    #[should_panic(expected = "axis 2 out of range for Point2")]
+    fn test_index_out_of_range() {
+        let _ = Point2::ORIGIN[2];
+    }
+
+    #[test]
+    fn test_float_tuples() {
+        let p = Point3::from((1.5, -0.0015, 2.0));
+        assert_eq!(p, Point3::new(Microns(1500), Microns(-1), Microns(2000)));
+        assert_eq!(<(f32, f32, f32)>::from(p), (1.5, -0.001, 2.0));
+        assert_eq!(<(f64, f64, f64)>::from(p), (1.5, -0.001, 2.0));
+        let v = Vec2::try_from((0.25, -1.0)).unwrap();
+        assert_eq!(v, Vec2::new(Microns(250), Microns(-1000)));
+        assert_eq!(Vec2::try_from((f64::NAN, 0.0)), Err(ConversionError::NaN));
+        assert_eq!(Point2::try_from((0.0, 1e7)), Err(ConversionError::Overflow));
+    }
+
+    #[test]
+    fn test_dot_and_cross() {
+        let i = Vec3::new(Microns(2), Microns(0), Microns(0));
+        let j = Vec3::new(Microns(0), Microns(3), Microns(0));
+        assert_eq!(i.dot(j), SquareMicrons(0));
+        assert_eq!(i.dot(i + j), SquareMicrons(4));
+        assert_eq!(i.cross(j), [0, 0, 6].map(SquareMicrons));
+        assert_eq!(j.cross(i), [0, 0, -6].map(SquareMicrons));
+        let a = Vec2::new(Microns(3), Microns(1));
+        let b = Vec2::new(Microns(1), Microns(2));
+        assert_eq!(
+            (a.cross(b), b.cross(a)),
+            (SquareMicrons(5), SquareMicrons(-5))
+        );
+        assert_eq!(a.cross(a * 7), SquareMicrons(0));
+
+        let max = Vec2::new(Microns::MAX, Microns::MAX);
+        assert_eq!(max.dot(-max), SquareMicrons(-2 * (i32::MAX as i64).pow(2)));
+        let min = Vec2::new(Microns::MIN, Microns::MIN);
+        assert_eq!(min.checked_dot(min), None);
+        assert_eq!(min.dot_i128(min), 1 << 63);
+        let widest = (1 << 62) + ((i32::MAX as i64) << 31);
+        let corner = Vec2::new(Microns::MAX, Microns::MIN);
+        assert_eq!(min.cross(corner), SquareMicrons(widest));
+        assert_eq!(corner.cross(min), SquareMicrons(-widest));
+        let far = Vec3::new(Microns::MIN, Microns::MIN, Microns::MAX);
+        let back = Vec3::new(Microns::MAX, Microns::MIN, Microns::MIN);
+        assert_eq!(far.cross(back)[0], SquareMicrons(widest));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_dot_overflow() {
+        let min = Vec3::new(Microns::MIN, Microns::MIN, Microns::MIN);
+        min.dot(min);
+    }
+
+    #[test]
+    fn test_widened_products() {
+        let big = Vec3::new(Microns::MIN, Microns::MIN, Microns::MIN);
+        assert_eq!(big.norm_sq_i128(), 3 * (1i128 << 62));
+        let v = Vec3::new(Microns::MAX, Microns(-2), Microns(0));
+        assert_eq!(
+            big.dot_i128(v),
+            -(1i128 << 62) + (1i128 << 31) + (1i128 << 32)
+        );
+        let w = Vec2::new(Microns(3_000), Microns(-4_000));
+        assert_eq!(w.norm_sq_i128(), 25_000_000);
+        #[cfg(feature = "std")]
+        assert_eq!(w.norm_mm(), 5.0);
+        assert_eq!(w.dot_i128(Vec2::new(Microns(4), Microns(3))), 0);
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv() {
+        let toolpath = vec![
+            Point3::new(Microns(-1), Microns::MAX, Microns(200)),
+            Point3::new(Microns(12_345), Microns::MIN, Microns(400)),
+        ];
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&toolpath).unwrap();
+        let archived =
+            rkyv::access::<rkyv::Archived<Vec<Point3>>, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 2);
+        assert!(archived[0] == toolpath[0]);
+        assert_eq!(archived[1].y, Microns::MIN);
+        let back: Vec<Point3> = rkyv::deserialize::<_, rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(back, toolpath);
+        let v = Vec2::new(Microns(3), Microns(-4));
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&v).unwrap();
+        let archived = rkyv::access::<rkyv::Archived<Vec2>, rkyv::rancor::Error>(&bytes).unwrap();
+        let back: Vec2 = rkyv::deserialize::<_, rkyv::rancor::Error>(archived).unwrap();
+        assert_eq!(back, v);
+    }
+}
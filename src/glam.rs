@@ -0,0 +1,83 @@
+//! Conversions to and from `glam` vectors.
+//!
+//! `IVec2`/`IVec3` hold exact micrometers. `Vec2`/`Vec3` and `DVec2`/`DVec3`
+//! hold millimeters, truncated toward zero on the way back. glam has no
+//! separate point type, so points and vectors both convert.
+use crate::{ConversionError, Microns, Point2, Point3, Vec2, Vec3};
+
+macro_rules! impl_glam {
+    ($($ours:ident => $ivec:ident, $vec:ident, $dvec:ident { $($c:ident),* };)*) => {$(
+        impl From<$ours> for glam::$ivec {
+            fn from(p: $ours) -> Self {
+                glam::$ivec::new($(p.$c.0),*)
+            }
+        }
+
+        impl From<glam::$ivec> for $ours {
+            fn from(v: glam::$ivec) -> Self {
+                $ours { $($c: Microns(v.$c)),* }
+            }
+        }
+
+        /// In millimeters.
+        impl From<$ours> for glam::$vec {
+            fn from(p: $ours) -> Self {
+                glam::$vec::new($(f32::from(p.$c)),*)
+            }
+        }
+
+        /// From millimeters, truncating each coordinate toward zero.
+        impl TryFrom<glam::$vec> for $ours {
+            type Error = ConversionError;
+            fn try_from(v: glam::$vec) -> Result<Self, ConversionError> {
+                Ok($ours { $($c: Microns::try_from_f32(v.$c)?),* })
+            }
+        }
+
+        /// In millimeters.
+        impl From<$ours> for glam::$dvec {
+            fn from(p: $ours) -> Self {
+                glam::$dvec::new($(f64::from(p.$c)),*)
+            }
+        }
+
+        /// From millimeters, truncating each coordinate toward zero.
+        impl TryFrom<glam::$dvec> for $ours {
+            type Error = ConversionError;
+            fn try_from(v: glam::$dvec) -> Result<Self, ConversionError> {
+                Ok($ours { $($c: Microns::try_from(v.$c)?),* })
+            }
+        }
+    )*};
+}
+impl_glam! {
+    Point2 => IVec2, Vec2, DVec2 { x, y };
+    Vec2 => IVec2, Vec2, DVec2 { x, y };
+    Point3 => IVec3, Vec3, DVec3 { x, y, z };
+    Vec3 => IVec3, Vec3, DVec3 { x, y, z };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glam_conversions() {
+        let p = Point3::new(Microns(-1), Microns(2500), Microns::MIN);
+        assert_eq!(glam::IVec3::from(p), glam::IVec3::new(-1, 2500, i32::MIN));
+        assert_eq!(Point3::from(glam::IVec3::from(p)), p);
+        let v = Vec2::new(Microns(1500), Microns(-250));
+        assert_eq!(glam::Vec2::from(v), glam::Vec2::new(1.5, -0.25));
+        assert_eq!(Vec2::try_from(glam::Vec2::from(v)), Ok(v));
+        let d = glam::DVec3::from(Vec3::new(Microns(1), Microns(2), Microns(3)));
+        assert_eq!(d, glam::DVec3::new(0.001, 0.002, 0.003));
+        assert_eq!(
+            Point3::try_from(d * 2.0),
+            Ok(Point3::new(Microns(2), Microns(4), Microns(6)))
+        );
+        assert_eq!(
+            Point2::try_from(glam::DVec2::new(1e10, 0.0)),
+            Err(ConversionError::Overflow)
+        );
+    }
+}
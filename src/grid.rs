@@ -0,0 +1,238 @@
+//! Finding points near a position by hashing them into a grid of
+//! cells.
+use crate::{geom::MicronPoint, Microns};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+type Cell = [i64; 3];
+
+/// A spatial hash of Point2 or Point3 values in square or cubic cells, for
+/// snapping and merging near-coincident vertices.
+///
+/// Points are identified by the index `insert` returned. Cells are exact
+/// integer divisions of the coordinates and distances are compared exactly,
+/// so a query never misses a point on its boundary.
+///
+/// ```
+/// use microns::{Microns, Point2, SpatialGrid};
+///
+/// let mut grid = SpatialGrid::new(Microns(100));
+/// let a = grid.insert(Point2::new(Microns(0), Microns(0)));
+/// let b = grid.insert(Point2::new(Microns(30), Microns(40)));
+/// grid.insert(Point2::new(Microns(500), Microns(0)));
+/// let probe = Point2::new(Microns(10), Microns(0));
+/// assert_eq!(grid.within(probe, Microns(50)), [a, b]);
+/// assert_eq!(grid.nearest(probe), Some(a));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpatialGrid<P> {
+    cell_size: Microns,
+    points: Vec<P>,
+    cells: HashMap<Cell, Vec<usize>>,
+}
+
+impl<P: MicronPoint> SpatialGrid<P> {
+    /// # Panics
+    ///
+    /// If `cell_size` isn't positive.
+    pub fn new(cell_size: Microns) -> Self {
+        assert!(cell_size > Microns::ZERO, "cell size must be positive");
+        SpatialGrid {
+            cell_size,
+            points: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Adds `p` and returns its index, counting up from zero.
+    pub fn insert(&mut self, p: P) -> usize {
+        let index = self.points.len();
+        self.points.push(p);
+        self.cells.entry(self.cell_of(&p)).or_default().push(index);
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Every point inserted, by index.
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    /// Indices of the points at most `radius` from `center`, in ascending
+    /// order.
+    pub fn within(&self, center: P, radius: Microns) -> Vec<usize> {
+        if radius < Microns::ZERO {
+            return Vec::new();
+        }
+        let radius_sq = (radius.0 as i128).pow(2);
+        let (mut lo, mut hi) = ([0; 3], [0; 3]);
+        for axis in 0..P::DIM {
+            let c = center.coord(axis).0 as i64;
+            lo[axis] = self.axis_cell(c - radius.0 as i64);
+            hi[axis] = self.axis_cell(c + radius.0 as i64);
+        }
+        let mut found: Vec<usize> = self
+            .cells_in(lo, hi)
+            .into_iter()
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .filter(|&i| self.points[i].distance_sq(&center) <= radius_sq)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    /// The index of the point closest to `p`, the lowest index on a tie, or
+    /// `None` if the grid is empty.
+    pub fn nearest(&self, p: P) -> Option<usize> {
+        let closest = |indices: &mut dyn Iterator<Item = usize>| {
+            indices.map(|i| (self.points[i].distance_sq(&p), i)).min()
+        };
+        let center = self.cell_of(&p);
+        let mut best: Option<(i128, usize)> = None;
+        for ring in 0i64.. {
+            let (mut lo, mut hi) = (center, center);
+            for axis in 0..P::DIM {
+                lo[axis] -= ring;
+                hi[axis] += ring;
+            }
+            if Self::box_len(lo, hi) >= self.cells.len() as u128 {
+                // the rings have outgrown the occupied cells: finish by brute force
+                return closest(&mut (0..self.points.len())).map(|(_, i)| i);
+            }
+            let shell = self
+                .cells_in(lo, hi)
+                .into_iter()
+                .filter(|(cell, _)| (0..P::DIM).any(|a| (cell[a] - center[a]).abs() == ring));
+            let found = closest(&mut shell.flat_map(|(_, indices)| indices.iter().copied()));
+            best = match (best, found) {
+                (Some(b), Some(f)) => Some(b.min(f)),
+                (b, f) => b.or(f),
+            };
+            // every point in a further ring is more than `ring` cells out
+            // along some axis
+            let reach = (ring as i128 * self.cell_size.0 as i128).pow(2);
+            if best.is_some_and(|(d, _)| d <= reach) {
+                break;
+            }
+        }
+        best.map(|(_, i)| i)
+    }
+
+    /// The number of cells in the box from `lo` to `hi` inclusive.
+    fn box_len(lo: Cell, hi: Cell) -> u128 {
+        (0..P::DIM).map(|a| (hi[a] - lo[a] + 1) as u128).product()
+    }
+
+    /// The occupied cells in the box from `lo` to `hi` inclusive, looked up
+    /// one by one or found by scanning, whichever is less work.
+    fn cells_in(&self, lo: Cell, hi: Cell) -> Vec<(Cell, &[usize])> {
+        let inside = |cell: &Cell| (0..P::DIM).all(|a| (lo[a]..=hi[a]).contains(&cell[a]));
+        if Self::box_len(lo, hi) > self.cells.len() as u128 {
+            return self
+                .cells
+                .iter()
+                .filter(|(cell, _)| inside(cell))
+                .map(|(&cell, indices)| (cell, indices.as_slice()))
+                .collect();
+        }
+        let mut found = Vec::new();
+        let mut cell = lo;
+        loop {
+            if let Some(indices) = self.cells.get(&cell) {
+                found.push((cell, indices.as_slice()));
+            }
+            // step through the box like an odometer
+            let mut axis = 0;
+            while axis < P::DIM && cell[axis] == hi[axis] {
+                cell[axis] = lo[axis];
+                axis += 1;
+            }
+            if axis == P::DIM {
+                return found;
+            }
+            cell[axis] += 1;
+        }
+    }
+
+    fn axis_cell(&self, c: i64) -> i64 {
+        c.div_euclid(self.cell_size.0 as i64)
+    }
+
+    fn cell_of(&self, p: &P) -> Cell {
+        let mut cell = [0; 3];
+        for (axis, c) in cell.iter_mut().enumerate().take(P::DIM) {
+            *c = self.axis_cell(p.coord(axis).0 as i64);
+        }
+        cell
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point2, Point3};
+
+    /// A deterministic scatter, clustered near the origin with a few far
+    /// outliers.
+    fn scatter(n: usize) -> impl Iterator<Item = [i32; 3]> {
+        let mut state = 0x2545_f491_u64;
+        core::iter::repeat_with(move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1);
+            let v = (state >> 33) as i32;
+            let spread = if v % 17 == 0 { i32::MAX } else { 5_000 };
+            [v % spread, (v >> 7) % spread, (v >> 13) % 800]
+        })
+        .take(n)
+    }
+
+    fn brute_nearest<P: MicronPoint>(points: &[P], p: P) -> Option<usize> {
+        (0..points.len()).min_by_key(|&i| (points[i].distance_sq(&p), i))
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut grid2 = SpatialGrid::new(Microns(250));
+        let mut grid3 = SpatialGrid::new(Microns(97));
+        for [x, y, z] in scatter(400) {
+            grid2.insert(Point2::new(Microns(x), Microns(y)));
+            grid3.insert(Point3::new(Microns(x), Microns(y), Microns(z)));
+        }
+        for [x, y, z] in scatter(60) {
+            let p2 = Point2::new(Microns(y), Microns(x));
+            let p3 = Point3::new(Microns(y), Microns(x), Microns(z));
+            assert_eq!(grid2.nearest(p2), brute_nearest(grid2.points(), p2));
+            assert_eq!(grid3.nearest(p3), brute_nearest(grid3.points(), p3));
+            let r = Microns(x.rem_euclid(1_000));
+            let want: Vec<_> = (0..grid3.len())
+                .filter(|&i| grid3.points()[i].distance_sq(&p3) <= (r.0 as i128).pow(2))
+                .collect();
+            assert_eq!(grid3.within(p3, r), want);
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut grid = SpatialGrid::new(Microns(10));
+        let origin = Point2::ORIGIN;
+        assert_eq!((grid.nearest(origin), grid.is_empty()), (None, true));
+        let far = grid.insert(Point2::new(Microns::MIN, Microns::MAX));
+        assert_eq!(grid.nearest(origin), Some(far));
+        let a = grid.insert(Point2::new(Microns(10), Microns(0)));
+        let b = grid.insert(Point2::new(Microns(0), Microns(-10)));
+        assert_eq!(grid.within(origin, Microns(10)), [a, b]);
+        assert!(grid.within(origin, Microns(9)).is_empty());
+        assert!(grid.within(origin, Microns(-1)).is_empty());
+        assert_eq!(grid.nearest(origin), Some(a));
+        let corner = Point2::new(Microns::MIN + Microns(3), Microns::MAX - Microns(4));
+        assert_eq!(grid.within(corner, Microns(5)), [far]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Binning measured lengths to see how they are distributed.
+use crate::Microns;
+use alloc::{vec, vec::Vec};
+
+/// Counts of values in equal-width bins, for distributions of measured
+/// dimensions.
+///
+/// Bins are half-open, `[start, start + bin_width)`, and tile `[lo, hi)`;
+/// the last one is cut short at `hi` when the span isn't a whole number of
+/// bins. Values outside are counted separately, so none are lost.
+///
+/// ```
+/// use microns::{Histogram, Microns};
+///
+/// let mut h = Histogram::new(Microns(0), Microns(30), Microns(10));
+/// h.extend([5, 12, 15, 29, 30, -1].map(Microns));
+/// assert_eq!(h.counts(), [1, 2, 1]);
+/// assert_eq!((h.below(), h.above()), (1, 1));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Histogram {
+    lo: Microns,
+    hi: Microns,
+    bin_width: Microns,
+    counts: Vec<u64>,
+    below: u64,
+    above: u64,
+}
+
+impl Histogram {
+    /// # Panics
+    ///
+    /// If `hi <= lo` or `bin_width` isn't positive.
+    pub fn new(lo: Microns, hi: Microns, bin_width: Microns) -> Self {
+        assert!(hi > lo, "hi must be above lo");
+        assert!(bin_width > Microns::ZERO, "bin width must be positive");
+        let span = hi.0 as i64 - lo.0 as i64;
+        let bins = (span as u64).div_ceil(bin_width.0 as u64);
+        Histogram {
+            lo,
+            hi,
+            bin_width,
+            counts: vec![0; bins as usize],
+            below: 0,
+            above: 0,
+        }
+    }
+
+    /// The bin holding `m`, or `None` if it's outside every bin.
+    pub fn bin_of(&self, m: Microns) -> Option<usize> {
+        if m < self.lo || m >= self.hi {
+            return None;
+        }
+        let offset = m.0 as i64 - self.lo.0 as i64;
+        Some((offset / self.bin_width.0 as i64) as usize)
+    }
+
+    pub fn add(&mut self, m: Microns) {
+        match self.bin_of(m) {
+            Some(bin) => self.counts[bin] += 1,
+            None if m < self.lo => self.below += 1,
+            None => self.above += 1,
+        }
+    }
+
+    /// Counts per bin, lowest first.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Where bin `i` starts. The bin covers `[bin_start(i), bin_start(i + 1))`.
+    pub fn bin_start(&self, i: usize) -> Microns {
+        Microns((self.lo.0 as i64 + i as i64 * self.bin_width.0 as i64) as i32)
+    }
+
+    pub fn bin_width(&self) -> Microns {
+        self.bin_width
+    }
+
+    /// Values below `lo`.
+    pub fn below(&self) -> u64 {
+        self.below
+    }
+
+    /// Values at or above `hi`.
+    pub fn above(&self) -> u64 {
+        self.above
+    }
+
+    /// Every value added, including those outside the bins.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum::<u64>() + self.below + self.above
+    }
+
+    /// Each bin's start and count, lowest first.
+    pub fn iter(&self) -> impl Iterator<Item = (Microns, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| (self.bin_start(i), count))
+    }
+
+    /// Bins whose count is higher than both neighbors', lowest first; two
+    /// entries suggest a bimodal distribution. A run of equal counts at a
+    /// peak is reported once, at its first bin.
+    pub fn peaks(&self) -> Vec<usize> {
+        let mut peaks = Vec::new();
+        let mut i = 0;
+        while i < self.counts.len() {
+            let mut end = i + 1;
+            while end < self.counts.len() && self.counts[end] == self.counts[i] {
+                end += 1;
+            }
+            let rising = i == 0 || self.counts[i - 1] < self.counts[i];
+            let falling = end == self.counts.len() || self.counts[end] < self.counts[i];
+            if self.counts[i] > 0 && rising && falling {
+                peaks.push(i);
+            }
+            i = end;
+        }
+        peaks
+    }
+}
+
+impl Extend<Microns> for Histogram {
+    fn extend<I: IntoIterator<Item = Microns>>(&mut self, iter: I) {
+        for m in iter {
+            self.add(m);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binning() {
+        let mut h = Histogram::new(Microns(-25), Microns(0), Microns(10));
+        assert_eq!(h.counts().len(), 3);
+        h.extend([-26, -25, -16, -15, -6, -5, -1, 0, 4, 5, 6].map(Microns));
+        assert_eq!(h.counts(), [2, 2, 2]);
+        assert_eq!((h.below(), h.above(), h.total()), (1, 4, 11));
+        assert_eq!(h.bin_of(Microns(-1)), Some(2));
+        assert_eq!(h.bin_of(Microns(0)), None);
+        let bins: Vec<_> = h.iter().map(|(start, _)| start).collect();
+        assert_eq!(bins, [Microns(-25), Microns(-15), Microns(-5)]);
+
+        let mut full = Histogram::new(Microns::MIN, Microns::MAX, Microns::MAX);
+        full.extend([Microns::MIN, Microns(-1), Microns(0), Microns::MAX]);
+        assert_eq!(full.counts(), [1, 2, 0]);
+        assert_eq!(full.above(), 1);
+    }
+
+    #[test]
+    fn test_peaks() {
+        let mut h = Histogram::new(Microns(0), Microns(80), Microns(10));
+        h.extend([0, 12, 13, 14, 25, 41, 52, 53, 55, 57, 64, 66].map(Microns));
+        assert_eq!(h.counts(), [1, 3, 1, 0, 1, 4, 2, 0]);
+        assert_eq!(h.peaks(), [1, 5]);
+        let mut flat = Histogram::new(Microns(0), Microns(30), Microns(10));
+        assert!(flat.peaks().is_empty());
+        flat.extend([1, 11].map(Microns));
+        assert_eq!(flat.peaks(), [0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "bin width must be positive")]
+    fn test_zero_width() {
+        Histogram::new(Microns(0), Microns(1), Microns(0));
+    }
+}
@@ -0,0 +1,158 @@
+//! Interpolation between Microns.
+//!
+//! Results are computed exactly and truncated toward zero once at the end,
+//! the same as `Mul<f32>`, so the endpoints always come back unchanged.
+use crate::{exact, FixedDecimal, RoundingMode};
+use core::ops::RangeInclusive;
+
+/// Shapes of `t` for `Microns::lerp_eased`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// `3t² - 2t³`: zero velocity at both ends.
+    SmoothStep,
+    /// `6t⁵ - 15t⁴ + 10t³`: zero velocity and acceleration at both ends.
+    SmootherStep,
+    /// `t²`: starts at rest.
+    EaseIn,
+    /// `1 - (1 - t)²`: ends at rest.
+    EaseOut,
+}
+
+impl Easing {
+    /// Maps `t`, clamped to `0..=1`, onto the curve. The ends map to
+    /// exactly `0.0` and `1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::SmoothStep => t * t * (3.0 - 2.0 * t),
+            Easing::SmootherStep => t * t * t * (t * (t * 6.0 - 15.0) + 10.0),
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+        }
+    }
+}
+
+// Generic over the scale: only the backing integer matters here.
+macro_rules! impl_interp {
+    ($t:ty) => {
+        impl<const SCALE: u32> FixedDecimal<SCALE, $t> {
+            /// The value `t` of the way from `self` to `other`; `t` outside `0..=1`
+            /// extrapolates. Panics if `t` is NaN or the result is out of range.
+            pub fn lerp(self, other: Self, t: f32) -> Self {
+                let span = other.0 as i128 - self.0 as i128;
+                exact::mul_add(span, t, self.0 as i128, RoundingMode::Trunc)
+                    .and_then(exact::narrow)
+                    .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            /// Like `lerp`, with `t` clamped to `0..=1` and shaped by `easing`.
+            pub fn lerp_eased(self, other: Self, t: f32, easing: Easing) -> Self {
+                self.lerp(other, easing.apply(t))
+            }
+
+            pub fn smoothstep(self, other: Self, t: f32) -> Self {
+                self.lerp_eased(other, t, Easing::SmoothStep)
+            }
+
+            pub fn smootherstep(self, other: Self, t: f32) -> Self {
+                self.lerp_eased(other, t, Easing::SmootherStep)
+            }
+
+            /// How far `self` is from `start` toward `end`, so that
+            /// `start.lerp(end, m.inverse_lerp(start, end))` is close to `m`.
+            /// NaN or infinite if `start == end`.
+            pub fn inverse_lerp(self, start: Self, end: Self) -> f32 {
+                let offset = self.0 as f64 - start.0 as f64;
+                let span = end.0 as f64 - start.0 as f64;
+                (offset / span) as f32
+            }
+
+            /// Maps `self` from `from` onto `to` linearly, in exact integer math.
+            /// The ends of `from` map exactly to the ends of `to`.
+            /// Panics if the ends of `from` are equal, or, with a 64-bit
+            /// backing, if the spans are too wide to multiply in `i128`.
+            pub fn remap(self, from: RangeInclusive<Self>, to: RangeInclusive<Self>) -> Self {
+                let (from_start, from_end) = from.into_inner();
+                let (to_start, to_end) = to.into_inner();
+                let den = from_end.0 as i128 - from_start.0 as i128;
+                assert!(den != 0, "remap from a zero-width range");
+                let num = (self.0 as i128 - from_start.0 as i128)
+                    .checked_mul(to_end.0 as i128 - to_start.0 as i128)
+                    .expect("Value out of range");
+                let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+                let val =
+                    RoundingMode::Trunc.round_parts(to_start.0 as i128 + num / den, num % den, den);
+                exact::narrow(val).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+        }
+    };
+}
+impl_interp!(i32);
+impl_interp!(i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Microns;
+
+    #[test]
+    fn test_lerp() {
+        let (a, b) = (Microns(-10), Microns(10));
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Microns(0));
+        assert_eq!(a.lerp(b, 0.55), Microns(1));
+        assert_eq!(a.lerp(b, 2.0), Microns(30));
+        assert_eq!(Microns::MIN.lerp(Microns::MAX, 1.0), Microns::MAX);
+        assert_eq!(Microns::MAX.lerp(Microns::MIN, 1.0), Microns::MIN);
+        assert_eq!(Microns::MIN.lerp(Microns::MAX, 0.5), Microns(0));
+    }
+
+    #[test]
+    fn test_easing() {
+        use Easing::*;
+        let (a, b) = (Microns(0), Microns(1000));
+        for easing in [Linear, SmoothStep, SmootherStep, EaseIn, EaseOut] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(a.lerp_eased(b, -1.0, easing), a);
+            assert_eq!(a.lerp_eased(b, 2.0, easing), b);
+        }
+        assert_eq!(a.smoothstep(b, 0.5), Microns(500));
+        assert_eq!(a.smoothstep(b, 0.25), Microns(156));
+        assert_eq!(a.smootherstep(b, 0.25), Microns(103));
+        assert_eq!(a.lerp_eased(b, 0.5, EaseIn), Microns(250));
+        assert_eq!(a.lerp_eased(b, 0.5, EaseOut), Microns(750));
+    }
+
+    #[test]
+    fn test_inverse_lerp() {
+        let (a, b) = (Microns(1000), Microns(3000));
+        assert_eq!(Microns(2000).inverse_lerp(a, b), 0.5);
+        assert_eq!(a.inverse_lerp(a, b), 0.0);
+        assert_eq!(b.inverse_lerp(a, b), 1.0);
+        assert_eq!(Microns(0).inverse_lerp(a, b), -0.5);
+        assert!(a.inverse_lerp(a, a).is_nan());
+    }
+
+    #[test]
+    fn test_remap() {
+        let (from, to) = (Microns(0)..=Microns(3), Microns(100)..=Microns(200));
+        assert_eq!(Microns(0).remap(from.clone(), to.clone()), Microns(100));
+        assert_eq!(Microns(3).remap(from.clone(), to.clone()), Microns(200));
+        assert_eq!(Microns(1).remap(from.clone(), to.clone()), Microns(133));
+        assert_eq!(
+            Microns(1).remap(from, Microns(200)..=Microns(100)),
+            Microns(166)
+        );
+        let full = Microns::MIN..=Microns::MAX;
+        assert_eq!(
+            Microns::MAX.remap(full.clone(), Microns(0)..=Microns(10)),
+            Microns(10)
+        );
+        assert_eq!(Microns(5).remap(Microns(0)..=Microns(10), full), Microns(0));
+    }
+}
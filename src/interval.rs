@@ -0,0 +1,276 @@
+//! Closed intervals of Microns, for tolerance stack-ups.
+//!
+//! Arithmetic on intervals gives every value the operands could combine
+//! to: `[a, b] + [c, d] = [a + c, b + d]`, `[a, b] - [c, d] = [a - d, b - c]`.
+//! Scaling by a float rounds the ends outward, so the result never drops a
+//! value the exact product would include.
+use crate::{exact, Microns, RoundingMode};
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// The values `lo..=hi`. Always non-empty: `lo <= hi`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MicronsInterval {
+    lo: Microns,
+    hi: Microns,
+}
+
+impl MicronsInterval {
+    /// The interval between `a` and `b`, in either order.
+    pub const fn new(a: Microns, b: Microns) -> Self {
+        MicronsInterval {
+            lo: a.min(b),
+            hi: a.max(b),
+        }
+    }
+
+    /// Just `m`.
+    pub const fn point(m: Microns) -> Self {
+        MicronsInterval { lo: m, hi: m }
+    }
+
+    /// `nominal ± tol`; the sign of `tol` is ignored. Panics if an end
+    /// doesn't fit in Microns.
+    pub const fn around(nominal: Microns, tol: Microns) -> Self {
+        let tol = tol.abs();
+        MicronsInterval {
+            lo: nominal.checked_sub(tol).expect("Value out of range"),
+            hi: nominal.checked_add(tol).expect("Value out of range"),
+        }
+    }
+
+    pub const fn lo(self) -> Microns {
+        self.lo
+    }
+
+    pub const fn hi(self) -> Microns {
+        self.hi
+    }
+
+    /// `hi - lo`. Panics if that doesn't fit in Microns.
+    pub const fn width(self) -> Microns {
+        self.hi.checked_sub(self.lo).expect("Value out of range")
+    }
+
+    /// The middle of the interval, rounded toward zero.
+    pub const fn midpoint(self) -> Microns {
+        self.lo.midpoint(self.hi)
+    }
+
+    pub const fn contains(self, m: Microns) -> bool {
+        self.lo.0 <= m.0 && m.0 <= self.hi.0
+    }
+
+    /// Whether all of `other` lies inside `self`.
+    pub const fn contains_interval(self, other: MicronsInterval) -> bool {
+        self.lo.0 <= other.lo.0 && other.hi.0 <= self.hi.0
+    }
+
+    /// The values in both, if any.
+    pub const fn intersection(self, other: MicronsInterval) -> Option<Self> {
+        let lo = self.lo.max(other.lo);
+        let hi = self.hi.min(other.hi);
+        if lo.0 <= hi.0 {
+            Some(MicronsInterval { lo, hi })
+        } else {
+            None
+        }
+    }
+
+    /// The smallest interval covering both. Also covers any gap between
+    /// them, since a union of disjoint intervals isn't an interval.
+    pub const fn union(self, other: MicronsInterval) -> Self {
+        MicronsInterval {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+}
+
+impl From<Microns> for MicronsInterval {
+    fn from(m: Microns) -> Self {
+        MicronsInterval::point(m)
+    }
+}
+
+/// Panics if an end doesn't fit in Microns, in release builds too, so the
+/// ends can't wrap past each other. The same goes for `-` and `*`.
+impl Add for MicronsInterval {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        MicronsInterval {
+            lo: self.lo.checked_add(rhs.lo).expect("Value out of range"),
+            hi: self.hi.checked_add(rhs.hi).expect("Value out of range"),
+        }
+    }
+}
+
+impl Add<Microns> for MicronsInterval {
+    type Output = Self;
+    fn add(self, rhs: Microns) -> Self {
+        self + MicronsInterval::point(rhs)
+    }
+}
+
+impl Sub for MicronsInterval {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        MicronsInterval {
+            lo: self.lo.checked_sub(rhs.hi).expect("Value out of range"),
+            hi: self.hi.checked_sub(rhs.lo).expect("Value out of range"),
+        }
+    }
+}
+
+impl Sub<Microns> for MicronsInterval {
+    type Output = Self;
+    fn sub(self, rhs: Microns) -> Self {
+        self - MicronsInterval::point(rhs)
+    }
+}
+
+impl Neg for MicronsInterval {
+    type Output = Self;
+    fn neg(self) -> Self {
+        MicronsInterval {
+            lo: self.hi.checked_neg().expect("Value out of range"),
+            hi: self.lo.checked_neg().expect("Value out of range"),
+        }
+    }
+}
+
+impl Mul<i32> for MicronsInterval {
+    type Output = Self;
+    fn mul(self, rhs: i32) -> Self {
+        let end = |m: Microns| m.checked_mul_i32(rhs).expect("Value out of range");
+        MicronsInterval::new(end(self.lo), end(self.hi))
+    }
+}
+
+/// Computed exactly, with the ends rounded outward.
+impl Mul<f32> for MicronsInterval {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        let (a, b) = if rhs < 0.0 {
+            (self.hi, self.lo)
+        } else {
+            (self.lo, self.hi)
+        };
+        let scale = |m: Microns, mode| {
+            exact::mul(m.0 as i128, rhs, mode)
+                .and_then(exact::narrow)
+                .unwrap_or_else(|e| panic!("Value out of range: {e}"))
+        };
+        MicronsInterval {
+            lo: scale(a, RoundingMode::Floor),
+            hi: scale(b, RoundingMode::Ceil),
+        }
+    }
+}
+
+impl AddAssign for MicronsInterval {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for MicronsInterval {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for MicronsInterval {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(MicronsInterval::new(u.arbitrary()?, u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(Microns, Microns)>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+        let bytes = [0xff, 0xff, 0xff, 0x7f, 0, 0, 0, 0x80, 1, 2, 3];
+        let mut u = Unstructured::new(&bytes);
+        let i = MicronsInterval::arbitrary(&mut u).unwrap();
+        assert_eq!((i.lo(), i.hi()), (Microns::MIN, Microns::MAX));
+        let t = crate::Toleranced::arbitrary(&mut u).unwrap();
+        assert!(t.plus >= Microns::ZERO && t.minus >= Microns::ZERO);
+    }
+
+    #[test]
+    fn test_interval_ops() {
+        let a = MicronsInterval::around(Microns(10_000), Microns(50));
+        let b = MicronsInterval::new(Microns(2_020), Microns(1_980));
+        assert_eq!((a.lo(), a.hi()), (Microns(9_950), Microns(10_050)));
+        assert_eq!(a.width(), Microns(100));
+        let sum = a + b;
+        assert_eq!((sum.lo(), sum.hi()), (Microns(11_930), Microns(12_070)));
+        let diff = a - b;
+        assert_eq!((diff.lo(), diff.hi()), (Microns(7_930), Microns(8_070)));
+        assert_eq!(diff.midpoint(), Microns(8_000));
+        assert_eq!(-b, MicronsInterval::new(Microns(-2_020), Microns(-1_980)));
+        assert_eq!(
+            b * -2,
+            MicronsInterval::new(Microns(-4_040), Microns(-3_960))
+        );
+        let scaled = MicronsInterval::new(Microns(1), Microns(2)) * 0.3;
+        assert_eq!((scaled.lo(), scaled.hi()), (Microns(0), Microns(1)));
+        let flipped = MicronsInterval::new(Microns(1), Microns(2)) * -0.3;
+        assert_eq!((flipped.lo(), flipped.hi()), (Microns(-1), Microns(0)));
+        let mut acc = MicronsInterval::point(Microns::ZERO);
+        acc += a;
+        acc -= a;
+        assert_eq!(acc, MicronsInterval::around(Microns::ZERO, Microns(100)));
+    }
+
+    #[test]
+    fn test_interval_sets() {
+        let a = MicronsInterval::new(Microns(0), Microns(10));
+        let b = MicronsInterval::new(Microns(5), Microns(20));
+        let c = MicronsInterval::new(Microns(30), Microns(40));
+        assert!(a.contains(Microns(10)) && !a.contains(Microns(11)));
+        assert_eq!(
+            a.intersection(b),
+            Some(MicronsInterval::new(Microns(5), Microns(10)))
+        );
+        assert_eq!(a.intersection(c), None);
+        assert_eq!(
+            a.intersection(MicronsInterval::point(Microns(10))),
+            Some(MicronsInterval::point(Microns(10)))
+        );
+        assert_eq!(a.union(c), MicronsInterval::new(Microns(0), Microns(40)));
+        assert!(a.union(b).contains_interval(a));
+        assert!(!a.contains_interval(b));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_width_overflow() {
+        MicronsInterval::new(Microns::MIN, Microns::MAX).width();
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_add_overflow() {
+        let _ = MicronsInterval::new(Microns(0), Microns::MAX) + Microns(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_neg_overflow() {
+        assert_eq!(
+            -MicronsInterval::new(Microns(-3), Microns::MAX),
+            MicronsInterval::new(-Microns::MAX, Microns(3))
+        );
+        let _ = -MicronsInterval::point(Microns::MIN);
+    }
+}
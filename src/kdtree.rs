@@ -0,0 +1,245 @@
+//! Nearest-neighbor and range queries over a fixed set of points.
+use crate::{geom::MicronPoint, Microns, Point2, Point3};
+use alloc::{collections::BinaryHeap, vec::Vec};
+
+/// A balanced k-d tree over a fixed set of points, for nearest-neighbor and
+/// range lookups in probe meshes and point clouds.
+///
+/// Results are indices into the slice the tree was built from. Distances
+/// are compared exactly, and ties go to the lowest index, so answers never
+/// depend on how the tree happened to split.
+///
+/// ```
+/// use microns::{KdTree2, Microns, Point2};
+///
+/// let p = |x, y| Point2::new(Microns(x), Microns(y));
+/// let tree = KdTree2::new(&[p(0, 0), p(10, 0), p(0, 10), p(50, 50)]);
+/// assert_eq!(tree.nearest(p(9, 2)), Some(1));
+/// assert_eq!(tree.nearest_k(p(1, 1), 3), [0, 1, 2]);
+/// assert_eq!(tree.within(p(0, 0), Microns(10)), [0, 1, 2]);
+/// assert_eq!(tree.in_box(p(5, -5), p(60, 60)), [1, 3]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    /// Each subtree is a range whose middle element splits the rest on the
+    /// subtree's axis, cycling through the axes with depth.
+    nodes: Vec<(P, usize)>,
+}
+
+pub type KdTree2 = KdTree<Point2>;
+pub type KdTree3 = KdTree<Point3>;
+
+impl<P: MicronPoint> KdTree<P> {
+    pub fn new(points: &[P]) -> Self {
+        let mut nodes: Vec<_> = points.iter().copied().zip(0..).collect();
+        Self::build(&mut nodes, 0);
+        KdTree { nodes }
+    }
+
+    fn build(nodes: &mut [(P, usize)], axis: usize) {
+        if nodes.len() <= 1 {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        nodes.select_nth_unstable_by_key(mid, |(p, _)| p.coord(axis));
+        let (left, right) = nodes.split_at_mut(mid);
+        let next = (axis + 1) % P::DIM;
+        Self::build(left, next);
+        Self::build(&mut right[1..], next);
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The index of the closest point, the lowest index on a tie, or `None`
+    /// if the tree is empty.
+    pub fn nearest(&self, p: P) -> Option<usize> {
+        self.nearest_k(p, 1).first().copied()
+    }
+
+    /// The indices of the `k` closest points, closest first, or every point
+    /// if there are fewer than `k`.
+    pub fn nearest_k(&self, p: P, k: usize) -> Vec<usize> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        Self::search_k(&self.nodes, 0, &p, k, &mut best);
+        best.into_sorted_vec().into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Keeps the `k` smallest `(distance², index)` pairs in the max-heap
+    /// `best`.
+    fn search_k(
+        nodes: &[(P, usize)],
+        axis: usize,
+        p: &P,
+        k: usize,
+        best: &mut BinaryHeap<(i128, usize)>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        let (split, index) = nodes[mid];
+        best.push((split.distance_sq(p), index));
+        if best.len() > k {
+            best.pop();
+        }
+        let gap = p.coord(axis).0 as i128 - split.coord(axis).0 as i128;
+        let (near, far) = if gap < 0 {
+            (&nodes[..mid], &nodes[mid + 1..])
+        } else {
+            (&nodes[mid + 1..], &nodes[..mid])
+        };
+        let next = (axis + 1) % P::DIM;
+        Self::search_k(near, next, p, k, best);
+        // equal distances beyond the split can still win on index
+        if best.len() < k || best.peek().is_some_and(|&(worst, _)| gap * gap <= worst) {
+            Self::search_k(far, next, p, k, best);
+        }
+    }
+
+    /// The indices of the points at most `radius` from `center`, in
+    /// ascending order.
+    pub fn within(&self, center: P, radius: Microns) -> Vec<usize> {
+        let mut found = Vec::new();
+        if radius >= Microns::ZERO {
+            let radius_sq = (radius.0 as i128).pow(2);
+            self.visit(
+                &self.nodes,
+                0,
+                &mut |p, axis| {
+                    let gap = center.coord(axis).0 as i64 - p.coord(axis).0 as i64;
+                    (gap <= radius.0 as i64, gap >= -(radius.0 as i64))
+                },
+                &mut |p, i| {
+                    if p.distance_sq(&center) <= radius_sq {
+                        found.push(i);
+                    }
+                },
+            );
+        }
+        found.sort_unstable();
+        found
+    }
+
+    /// The indices of the points with every coordinate between `lo`'s and
+    /// `hi`'s inclusive, in ascending order.
+    pub fn in_box(&self, lo: P, hi: P) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.visit(
+            &self.nodes,
+            0,
+            &mut |p, axis| {
+                (
+                    lo.coord(axis) <= p.coord(axis),
+                    p.coord(axis) <= hi.coord(axis),
+                )
+            },
+            &mut |p, i| {
+                if (0..P::DIM).all(|a| (lo.coord(a)..=hi.coord(a)).contains(&p.coord(a))) {
+                    found.push(i);
+                }
+            },
+        );
+        found.sort_unstable();
+        found
+    }
+
+    /// Walks the subtrees that `sides` says may hold matches, as `(left,
+    /// right)` for a split point and its axis, and offers each node to
+    /// `check`.
+    fn visit(
+        &self,
+        nodes: &[(P, usize)],
+        axis: usize,
+        sides: &mut impl FnMut(&P, usize) -> (bool, bool),
+        check: &mut impl FnMut(&P, usize),
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let mid = nodes.len() / 2;
+        let (split, index) = nodes[mid];
+        check(&split, index);
+        let (left, right) = sides(&split, axis);
+        let next = (axis + 1) % P::DIM;
+        if left {
+            self.visit(&nodes[..mid], next, sides, check);
+        }
+        if right {
+            self.visit(&nodes[mid + 1..], next, sides, check);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud(n: usize) -> Vec<Point3> {
+        let mut state = 0x9e37_79b9_u64;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1);
+                let v = (state >> 32) as i32;
+                // a coarse lattice so that ties are common
+                Point3::new(
+                    Microns(v % 40 * 25),
+                    Microns((v >> 8) % 40 * 25),
+                    Microns((v >> 16) % 5),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let points = cloud(500);
+        let tree = KdTree3::new(&points);
+        assert_eq!(tree.len(), 500);
+        for q in cloud(520).into_iter().skip(480) {
+            let mut order: Vec<usize> = (0..points.len()).collect();
+            order.sort_by_key(|&i| (points[i].distance_sq(&q), i));
+            assert_eq!(tree.nearest_k(q, 7), order[..7]);
+            assert_eq!(tree.nearest(q), Some(order[0]));
+            let r = Microns(60);
+            let mut ball: Vec<_> = order
+                .iter()
+                .copied()
+                .filter(|&i| points[i].distance_sq(&q) <= 3600)
+                .collect();
+            ball.sort_unstable();
+            assert_eq!(tree.within(q, r), ball);
+            let lo = Point3::new(q.x - Microns(100), q.y - Microns(30), Microns(1));
+            let hi = Point3::new(q.x + Microns(50), q.y, Microns(3));
+            let inside: Vec<_> = (0..points.len())
+                .filter(|&i| {
+                    (0..3).all(|a| (lo.coord(a)..=hi.coord(a)).contains(&points[i].coord(a)))
+                })
+                .collect();
+            assert_eq!(tree.in_box(lo, hi), inside);
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let empty = KdTree2::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(Point2::ORIGIN), None);
+        let far = Point2::new(Microns::MIN, Microns::MAX);
+        let tree = KdTree2::new(&[far, Point2::ORIGIN, far]);
+        assert_eq!(tree.nearest_k(far, 5), [0, 2, 1]);
+        assert!(tree.nearest_k(far, 0).is_empty());
+        assert!(tree.within(Point2::ORIGIN, Microns(-1)).is_empty());
+        assert_eq!(tree.in_box(far, far), [0, 2]);
+    }
+}
@@ -0,0 +1,141 @@
+//! Conversions to and from `kurbo` curve geometry.
+//!
+//! kurbo coordinates are millimeters. Curves come back as polylines: each
+//! segment is flattened to within a tolerance given in Microns, and every
+//! vertex truncated toward zero to whole micrometers.
+//!
+//! ```
+//! use microns::{kurbo, Microns, Point2};
+//! use ::kurbo::{Circle, Shape};
+//!
+//! let circle = Circle::new((0.0, 0.0), 5.0).to_path(1e-3);
+//! let loops = kurbo::flatten(&circle, Microns(10)).unwrap();
+//! assert_eq!(loops.len(), 1);
+//! assert!(loops[0].len() > 20);
+//! assert_eq!(loops[0].first(), loops[0].last());
+//! ```
+use crate::{ConversionError, Microns, Point2, Vec2};
+use ::kurbo::{BezPath, PathEl};
+use alloc::vec::Vec;
+
+/// In millimeters.
+impl From<Point2> for ::kurbo::Point {
+    fn from(p: Point2) -> Self {
+        ::kurbo::Point::new(f64::from(p.x), f64::from(p.y))
+    }
+}
+
+/// From millimeters, truncating each coordinate toward zero.
+impl TryFrom<::kurbo::Point> for Point2 {
+    type Error = ConversionError;
+    fn try_from(p: ::kurbo::Point) -> Result<Self, ConversionError> {
+        Ok(Point2::new(
+            Microns::try_from(p.x)?,
+            Microns::try_from(p.y)?,
+        ))
+    }
+}
+
+/// In millimeters.
+impl From<Vec2> for ::kurbo::Vec2 {
+    fn from(v: Vec2) -> Self {
+        ::kurbo::Vec2::new(f64::from(v.x), f64::from(v.y))
+    }
+}
+
+/// From millimeters, truncating each coordinate toward zero.
+impl TryFrom<::kurbo::Vec2> for Vec2 {
+    type Error = ConversionError;
+    fn try_from(v: ::kurbo::Vec2) -> Result<Self, ConversionError> {
+        Ok(Vec2::new(Microns::try_from(v.x)?, Microns::try_from(v.y)?))
+    }
+}
+
+/// A path through `points` in order, closed back to the first if `closed`.
+pub fn to_bezpath(points: &[Point2], closed: bool) -> BezPath {
+    let mut path = BezPath::new();
+    let mut points = points.iter().map(|&p| ::kurbo::Point::from(p));
+    if let Some(first) = points.next() {
+        path.move_to(first);
+        points.for_each(|p| path.line_to(p));
+        if closed {
+            path.close_path();
+        }
+    }
+    path
+}
+
+/// One polyline per subpath of `path`, with curves flattened to within
+/// `tolerance`. A closed subpath ends on its first point. Vertices that land
+/// on the same micrometer as the one before are dropped. Panics if
+/// `tolerance` isn't positive.
+pub fn flatten(path: &BezPath, tolerance: Microns) -> Result<Vec<Vec<Point2>>, ConversionError> {
+    assert!(tolerance > Microns::ZERO, "tolerance must be positive");
+    let mut polylines: Vec<Vec<Point2>> = Vec::new();
+    let mut result = Ok(());
+    ::kurbo::flatten(path, f64::from(tolerance), |el| {
+        let p = match el {
+            PathEl::MoveTo(p) => {
+                polylines.push(Vec::new());
+                p
+            }
+            PathEl::LineTo(p) => p,
+            PathEl::ClosePath => match polylines.last().and_then(|line| line.first()) {
+                Some(&first) => first.into(),
+                None => return,
+            },
+            // `flatten` only emits lines
+            _ => return,
+        };
+        match (Point2::try_from(p), polylines.last_mut()) {
+            (Ok(p), Some(line)) if line.last() != Some(&p) => line.push(p),
+            (Err(e), _) if result.is_ok() => result = Err(e),
+            _ => {}
+        }
+    });
+    result.map(|()| polylines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kurbo_round_trip() {
+        let square = [
+            Point2::new(Microns::ZERO, Microns::ZERO),
+            Point2::new(Microns::MM, Microns::ZERO),
+            Point2::new(Microns::MM, Microns(-1_500)),
+        ];
+        let path = to_bezpath(&square, true);
+        assert_eq!(path.elements().len(), 4);
+        let loops = flatten(&path, Microns(1)).unwrap();
+        assert_eq!(loops, [[square[0], square[1], square[2], square[0]]]);
+        assert_eq!(
+            flatten(&to_bezpath(&square, false), Microns(1)).unwrap(),
+            [square]
+        );
+        assert!(to_bezpath(&[], true).is_empty());
+        assert_eq!(
+            Point2::try_from(::kurbo::Point::new(0.0015, -0.0019)),
+            Ok(Point2::new(Microns(1), Microns(-1)))
+        );
+    }
+
+    #[test]
+    fn test_flatten_curve() {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.quad_to((1.0, 2.0), (2.0, 0.0));
+        let line = &flatten(&path, Microns(5)).unwrap()[0];
+        assert_eq!(line.first(), Some(&Point2::ORIGIN));
+        assert_eq!(
+            line.last(),
+            Some(&Point2::new(Microns(2000), Microns::ZERO))
+        );
+        assert!(line.len() > 5);
+        let mut far = BezPath::new();
+        far.move_to((1e10, 0.0));
+        assert_eq!(flatten(&far, Microns(5)), Err(ConversionError::Overflow));
+    }
+}
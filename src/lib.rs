@@ -1,89 +1,387 @@
-/// microns is a simple, dependency-free, library to handling floats as fixed precision ints.
-/// microns gets its name from converting millimeter formatted f32 to an int
-/// with 10e-6 precision, but can be used in any case where i32::MIN < float < i32::MAX.
-/// This is useful for working with CNC machines, 3D printers, or any situation where
-/// micron precision is adequate and representations are traditionally formatted as floats.
-use std::ops::{Add, Div, Mul, Sub};
+//! microns is a simple, dependency-free, library to handling floats as fixed precision ints.
+//! microns gets its name from converting millimeter formatted f32 to an int
+//! with 10e-6 precision, but can be used in any case where i32::MIN < float < i32::MAX.
+//! This is useful for working with CNC machines, 3D printers, or any situation where
+//! micron precision is adequate and representations are traditionally formatted as floats.
+#![cfg_attr(not(test), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use core::cmp::Ordering;
+use core::fmt;
+
+mod accumulator;
+#[cfg(feature = "approx")]
+mod approx;
+pub mod area;
+mod ascii;
+#[cfg(target_has_atomic = "32")]
+mod atomic;
+pub mod convert;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "alloc")]
+mod dedup;
+#[cfg(feature = "diesel")]
+mod diesel;
+#[cfg(feature = "euclid")]
+pub mod euclid;
+mod exact;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed;
+#[cfg(feature = "fixed")]
+mod fixed_point;
+mod float;
+mod format;
+mod geom;
+#[cfg(feature = "glam")]
+mod glam;
+#[cfg(feature = "std")]
+mod grid;
+#[cfg(feature = "alloc")]
+mod histogram;
+mod interp;
+mod interval;
+#[cfg(feature = "alloc")]
+mod kdtree;
+#[cfg(feature = "kurbo")]
+pub mod kurbo;
+#[cfg(feature = "lyon")]
+pub mod lyon;
+mod macros;
+#[cfg(feature = "nalgebra")]
+mod nalgebra;
+mod nano;
+#[cfg(feature = "num-traits")]
+mod num;
+pub mod overflow;
+#[cfg(feature = "rayon")]
+pub mod par;
+mod parse;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "prost")]
+pub mod prost;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "rand")]
+mod random;
+mod range;
+mod scale;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "serde")]
+pub mod serde_as;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+mod simd;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+pub mod stats;
+mod toleranced;
+mod tracked;
+mod units;
+mod unsigned;
+#[cfg(feature = "uom")]
+mod uom;
+pub mod volume;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use accumulator::MicronsAccumulator;
+pub use area::SquareMicrons;
+pub use ascii::BufferTooSmall;
+#[cfg(target_has_atomic = "32")]
+pub use atomic::AtomicMicrons;
+#[cfg(feature = "std")]
+pub use dedup::dedup_unordered_within;
+#[cfg(feature = "alloc")]
+pub use dedup::dedup_within;
+#[cfg(feature = "rkyv")]
+pub use fixed::ArchivedFixedDecimal;
+pub use fixed::FixedDecimal;
+pub use format::{Format, Unit};
+pub use geom::{MicronPoint, Point2, Point3, Vec2, Vec3};
+#[cfg(feature = "std")]
+pub use grid::SpatialGrid;
+#[cfg(feature = "alloc")]
+pub use histogram::Histogram;
+pub use interp::Easing;
+pub use interval::MicronsInterval;
+#[cfg(feature = "alloc")]
+pub use kdtree::{KdTree, KdTree2, KdTree3};
+#[doc(hidden)]
+pub use macros::__mm_literal;
+pub use nano::Nanons;
+pub use overflow::{Checked, Saturating};
+pub use parse::ParseMicronsError;
+#[cfg(feature = "rand")]
+pub use random::UniformFixedDecimal;
+pub use range::MicronsRange;
+pub use scale::{Percent, Ppm};
+pub use toleranced::{FeatureKind, Toleranced};
+pub use tracked::TrackedMicrons;
+pub use unsigned::UMicrons;
+pub use volume::CubicMicrons;
 
 pub fn works(val: f32) -> bool {
-    if val.is_nan() {
-        return false;
+    Microns::try_from_f32(val).is_ok()
+}
+
+/// Reasons a float can't be converted to Microns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConversionError {
+    NaN,
+    Overflow,
+    Underflow,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::NaN => write!(f, "value is NaN"),
+            ConversionError::Overflow => write!(f, "value is above Microns::MAX"),
+            ConversionError::Underflow => write!(f, "value is below Microns::MIN"),
+        }
+    }
+}
+
+impl core::error::Error for ConversionError {}
+
+/// How to round values that fall between two whole microns.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Toward zero, the behavior of `Microns::from(f32)`.
+    #[default]
+    Trunc,
+    /// Toward negative infinity.
+    Floor,
+    /// Toward positive infinity.
+    Ceil,
+    /// To nearest, ties away from zero.
+    HalfUp,
+    /// To nearest, ties to even.
+    HalfEven,
+}
+
+impl RoundingMode {
+    fn round_f32(self, val: f32) -> f32 {
+        match self {
+            RoundingMode::Trunc => float::trunc(val),
+            RoundingMode::Floor => float::floor(val),
+            RoundingMode::Ceil => float::ceil(val),
+            RoundingMode::HalfUp => float::round(val),
+            RoundingMode::HalfEven => float::round_ties_even(val),
+        }
+    }
+
+    /// Divides `num` by a nonzero `den`, rounding the quotient with `self`.
+    pub(crate) fn div_i128(self, num: i128, den: i128) -> i128 {
+        let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+        self.round_parts(num / den, num % den, den)
+    }
+
+    /// Rounds `whole + frac / den` with `self`, for `den > 0` and `|frac| < den`.
+    pub(crate) fn round_parts(self, whole: i128, frac: i128, den: i128) -> i128 {
+        if frac == 0 {
+            return whole;
+        }
+        // rewrite as floor + a fraction strictly between 0 and 1
+        let (floor, frac) = if frac < 0 {
+            (whole - 1, frac + den)
+        } else {
+            (whole, frac)
+        };
+        let up = match self {
+            RoundingMode::Trunc => floor < 0,
+            RoundingMode::Floor => false,
+            RoundingMode::Ceil => true,
+            RoundingMode::HalfUp | RoundingMode::HalfEven => {
+                match (frac as u128 * 2).cmp(&(den as u128)) {
+                    Ordering::Less => false,
+                    Ordering::Greater => true,
+                    Ordering::Equal if self == RoundingMode::HalfUp => floor >= 0,
+                    Ordering::Equal => floor % 2 != 0,
+                }
+            }
+        };
+        if up {
+            floor + 1
+        } else {
+            floor
+        }
     }
-    val > f32::from(Microns::MIN) && val < f32::from(Microns::MAX)
 }
 
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// Microns is a simple struct that holds a i32 value,
 /// meant to be converted from a float for simplified math.
-pub struct Microns(pub i32);
+pub type Microns = FixedDecimal<3, i32>;
+
+/// Builds Microns from a count of micrometers, so that `Microns(1500)`
+/// reads the same as it did when Microns was its own tuple struct.
+#[allow(non_snake_case)]
+pub const fn Microns(um: i32) -> Microns {
+    FixedDecimal(um)
+}
+
+/// The compact default, for firmware and anything within ±2.1 km.
+pub type Microns32 = Microns;
+/// 64-bit positions, for large-format machines and long cumulative travel.
+/// Shares the arithmetic, conversions, units and interpolation with
+/// Microns; the `_i32` helpers and the area/volume types are 32-bit only.
+pub type Microns64 = FixedDecimal<3, i64>;
 
 impl Microns {
-    pub const ZERO: Microns = Microns(0);
-    pub const MIN: Microns = Microns(i32::MIN);
-    pub const MAX: Microns = Microns(i32::MAX);
+    /// The raw value in micrometers.
+    pub const fn as_micrometers(self) -> i32 {
+        self.0
+    }
 
-    pub fn abs(&self) -> Self {
-        Microns(self.0.abs())
+    /// The value in micrometers, widened for math that needs headroom.
+    pub const fn to_i64(self) -> i64 {
+        self.0 as i64
     }
-}
-impl From<f32> for Microns {
-    fn from(other: f32) -> Self {
-        assert!(works(other), "Value out of range");
-        Microns((other * 1000.0).trunc() as i32)
+
+    pub const fn checked_mul_i32(self, rhs: i32) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(val) => Some(Microns(val)),
+            None => None,
+        }
     }
-}
 
-impl From<Microns> for f32 {
-    fn from(other: Microns) -> Self {
-        other.0 as f32 / 1000.0
+    /// Returns `None` if `rhs` is zero or the division overflows.
+    pub const fn checked_div_i32(self, rhs: i32) -> Option<Self> {
+        match self.0.checked_div(rhs) {
+            Some(val) => Some(Microns(val)),
+            None => None,
+        }
     }
-}
 
-impl Add for Microns {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
-        Microns(self.0 + rhs.0)
+    #[track_caller]
+    pub fn strict_mul_i32(self, rhs: i32) -> Self {
+        self.checked_mul_i32(rhs)
+            .expect("attempt to multiply with overflow")
     }
-}
 
-impl Add<f32> for Microns {
-    type Output = Self;
-    fn add(self, rhs: f32) -> Self {
-        self + Microns::from(rhs)
+    /// Panics if `rhs` is zero or the division overflows.
+    #[track_caller]
+    pub fn strict_div_i32(self, rhs: i32) -> Self {
+        assert!(rhs != 0, "attempt to divide by zero");
+        self.checked_div_i32(rhs)
+            .expect("attempt to divide with overflow")
     }
-}
 
-impl Sub for Microns {
-    type Output = Self;
-    fn sub(self, rhs: Self) -> Self {
-        Microns(self.0 - rhs.0)
+    pub const fn saturating_mul_i32(self, rhs: i32) -> Self {
+        Microns(self.0.saturating_mul(rhs))
     }
-}
 
-impl Sub<f32> for Microns {
-    type Output = Self;
-    fn sub(self, rhs: f32) -> Self {
-        self - Microns::from(rhs)
+    /// The f32, in millimeters, closest to `self` among those that convert
+    /// back to `self`, or simply the closest if none does. Unlike
+    /// `f32::from`, `Microns::from(m.to_nearest_f32()) == m` holds for every
+    /// `m` within `±16_384_000` (16.384 m); past that, f32 spacing exceeds a
+    /// micrometer and only some values survive the trip.
+    pub fn to_nearest_f32(self) -> f32 {
+        // Correctly rounded: m / 1000 is never close enough to an f32
+        // midpoint for the double rounding through f64 to matter.
+        let nearest = (self.0 as f64 / 1000.0) as f32;
+        if Microns::try_from_f32(nearest) == Ok(self) {
+            return nearest;
+        }
+        // Conversion truncates, so a value just short of `self` comes back
+        // one micron toward zero; step one f32 away from zero instead.
+        let away = if self.0 > 0 {
+            nearest.next_up()
+        } else {
+            nearest.next_down()
+        };
+        if Microns::try_from_f32(away) == Ok(self) {
+            away
+        } else {
+            nearest
+        }
     }
-}
 
-impl Mul<f32> for Microns {
-    type Output = Self;
-    fn mul(self, rhs: f32) -> Self {
-        Microns::from(f32::from(self) * rhs)
+    /// Whether `self` survives `Microns::from(self.to_nearest_f32())`.
+    pub fn is_exactly_representable_f32(self) -> bool {
+        Microns::try_from_f32(self.to_nearest_f32()) == Ok(self)
     }
 }
+// Integers are taken as a count of micrometers, the same as `Microns(i32)`.
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Microns {
+            fn from(other: $t) -> Self {
+                Microns(other as i32)
+            }
+        }
+    )*};
+}
+impl_from_int!(i8, u8, i16, u16);
+
+macro_rules! impl_try_from_int {
+    ($($t:ty),*) => {$(
+        impl TryFrom<$t> for Microns {
+            type Error = ConversionError;
+            fn try_from(other: $t) -> Result<Self, ConversionError> {
+                i32::try_from(other).map(Microns).map_err(|_| {
+                    if other > 0 {
+                        ConversionError::Overflow
+                    } else {
+                        ConversionError::Underflow
+                    }
+                })
+            }
+        }
+    )*};
+}
+impl_try_from_int!(i64, u32, i128);
 
-impl Div<f32> for Microns {
-    type Output = Self;
-    fn div(self, rhs: f32) -> Self {
-        Microns::from(f32::from(self) / rhs)
+/// Orders `lhs` against a converted float; values past the ends of the
+/// range compare beyond every Microns and NaN is unordered.
+fn cmp_converted(lhs: Microns, rhs: Result<Microns, ConversionError>) -> Option<Ordering> {
+    match rhs {
+        Ok(rhs) => Some(lhs.cmp(&rhs)),
+        Err(ConversionError::Overflow) => Some(Ordering::Less),
+        Err(ConversionError::Underflow) => Some(Ordering::Greater),
+        Err(ConversionError::NaN) => None,
     }
 }
 
+// Floats are compared by the Microns they convert to, i.e. truncated to
+// whole micrometers: `Microns(1) == 0.0015` holds, like
+// `Microns(1) == Microns::from(0.0015)`.
+macro_rules! impl_cmp_float {
+    ($t:ty, $convert:expr) => {
+        impl PartialEq<$t> for Microns {
+            fn eq(&self, other: &$t) -> bool {
+                self.partial_cmp(other) == Some(Ordering::Equal)
+            }
+        }
+
+        impl PartialOrd<$t> for Microns {
+            fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                cmp_converted(*self, $convert(*other))
+            }
+        }
+
+        impl PartialEq<Microns> for $t {
+            fn eq(&self, other: &Microns) -> bool {
+                other == self
+            }
+        }
+
+        impl PartialOrd<Microns> for $t {
+            fn partial_cmp(&self, other: &Microns) -> Option<Ordering> {
+                other.partial_cmp(self).map(Ordering::reverse)
+            }
+        }
+    };
+}
+impl_cmp_float!(f32, Microns::try_from_f32);
+impl_cmp_float!(f64, Microns::try_from);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,6 +432,75 @@ mod tests {
         assert_eq!(b, 0.001);
     }
 
+    #[test]
+    fn test_f64() {
+        assert_eq!(Microns::try_from(0.001f64), Ok(Microns(1)));
+        assert_eq!(Microns::try_from(-12.3456f64), Ok(Microns(-12345)));
+        // well past the ~16m where f32 stops resolving microns
+        assert_eq!(
+            Microns::try_from(2_000_000.001f64),
+            Ok(Microns(2_000_000_001))
+        );
+        assert_eq!(f64::from(Microns(2_000_000_001)), 2_000_000.001);
+        assert_eq!(f64::from(Microns::MIN), -2_147_483.648);
+        assert_eq!(Microns::try_from(2147483.6479f64), Ok(Microns::MAX));
+        assert_eq!(
+            Microns::try_from(2_147_483.648f64),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(Microns::try_from(-2147483.6489f64), Ok(Microns::MIN));
+        assert_eq!(
+            Microns::try_from(-2_147_483.649f64),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(Microns::try_from(f64::NAN), Err(ConversionError::NaN));
+    }
+
+    #[test]
+    fn test_f64_round_trip() {
+        let check = |m: i32| {
+            let m = Microns(m);
+            assert_eq!(Microns::try_from(f64::from(m)), Ok(m), "{m:?}");
+        };
+        (-1_000_000..=1_000_000).for_each(check);
+        (i32::MIN..=i32::MAX).step_by(9_973).for_each(check);
+        (i32::MAX - 1_000..=i32::MAX).for_each(check);
+        (i32::MIN..=i32::MIN + 1_000).for_each(check);
+        assert_eq!(Microns::try_from(1.001f64), Ok(Microns(1001)));
+        assert_eq!(Microns::try_from(-524.286f64), Ok(Microns(-524_286)));
+        assert_eq!(Microns::from_inch(0.3), Microns(7_620));
+        // only float noise snaps; a real sub-micron remainder still truncates
+        assert_eq!(Microns::try_from(1.000_999_9f64), Ok(Microns(1000)));
+        assert_eq!(Microns::try_from(-0.000_999_999_9f64), Ok(Microns(0)));
+        let check64 = |m: i64| {
+            let m = Microns64::new(m);
+            assert_eq!(Microns64::try_from(f64::from(m)), Ok(m), "{m:?}");
+        };
+        (-1i64 << 50..=1 << 50).step_by(1 << 37).for_each(check64);
+        (-1_000_000..=1_000_000).step_by(7).for_each(check64);
+    }
+
+    #[test]
+    fn test_from_int() {
+        assert_eq!(Microns::from(-5i8), Microns(-5));
+        assert_eq!(Microns::from(255u8), Microns(255));
+        assert_eq!(Microns::from(i16::MIN), Microns(-32768));
+        assert_eq!(Microns::from(u16::MAX), Microns(65535));
+        assert_eq!(Microns::try_from(-7i64), Ok(Microns(-7)));
+        assert_eq!(
+            Microns::try_from(1i64 << 31),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(Microns::try_from(i64::MIN), Err(ConversionError::Underflow));
+        assert_eq!(Microns::try_from(i32::MAX as u32), Ok(Microns::MAX));
+        assert_eq!(Microns::try_from(u32::MAX), Err(ConversionError::Overflow));
+        assert_eq!(Microns::try_from(i32::MIN as i128), Ok(Microns::MIN));
+        assert_eq!(
+            Microns::try_from(i128::MIN),
+            Err(ConversionError::Underflow)
+        );
+    }
+
     #[test]
     fn test_mul_f32() {
         let a = Microns(1);
@@ -154,6 +521,83 @@ mod tests {
         assert_eq!(c, Microns(5));
     }
 
+    #[test]
+    fn test_mul_div_f32_exact() {
+        let big = Microns(2_000_000_001);
+        assert_eq!(big * 0.5, Microns(1_000_000_000));
+        assert_eq!(Microns(1_000_000_001) * 2.0, Microns(2_000_000_002));
+        assert_eq!(Microns(1_600_000_003) * 1.25, Microns(2_000_000_003));
+        assert_eq!(Microns(-1_600_000_003) * 1.25, Microns(-2_000_000_003));
+        assert_eq!(big / 2.0, Microns(1_000_000_000));
+        assert_eq!(Microns(1_000_000_001) / 0.5, Microns(2_000_000_002));
+        assert_eq!(Microns(1_000_000_001) / 1.25, Microns(800_000_000));
+        assert_eq!(Microns(-1_000_000_001) / 1.25, Microns(-800_000_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_mul_f32_overflow() {
+        let _ = Microns::MAX * 1.5;
+    }
+
+    #[test]
+    fn test_mul_ratio() {
+        // 10mm through a 16:20 gear reduction
+        assert_eq!(Microns(10_000).mul_ratio(16, 20), Microns(8_000));
+        assert_eq!(Microns(10).mul_ratio(1, 3), Microns(3));
+        assert_eq!(Microns(-10).mul_ratio(1, 3), Microns(-3));
+        assert_eq!(
+            Microns(10).mul_ratio_with(1, 3, RoundingMode::Ceil),
+            Microns(4)
+        );
+        assert_eq!(
+            Microns(-5).mul_ratio_with(1, -2, RoundingMode::HalfEven),
+            Microns(2)
+        );
+        // the intermediate product is well outside i64
+        assert_eq!(Microns::MAX.mul_ratio(i64::MAX, i64::MAX), Microns::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn test_mul_ratio_zero_den() {
+        let _ = Microns(1).mul_ratio(1, 0);
+    }
+
+    #[test]
+    fn test_mul_add() {
+        assert_eq!(Microns(3).mul_add(0.5, Microns(1)), Microns(2));
+        // -1.5 + 2 truncates to 0; truncating the product first gives -1 + 2
+        assert_eq!(Microns(-3).mul_add(0.5, Microns(2)), Microns(0));
+        assert_eq!(Microns(-3) * 0.5 + Microns(2), Microns(1));
+        assert_eq!(Microns(1).mul_add(1e-40, Microns(-5)), Microns(-4));
+        assert_eq!(
+            Microns::MAX.mul_add(2.0, Microns::MIN),
+            Microns(i32::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn test_div_i128() {
+        use RoundingMode::*;
+        let cases = [
+            (7, 2, [3, 3, 4, 4, 4]),
+            (5, 2, [2, 2, 3, 3, 2]),
+            (-5, 2, [-2, -3, -2, -3, -2]),
+            (5, -2, [-2, -3, -2, -3, -2]),
+            (-7, -3, [2, 2, 3, 2, 2]),
+            (6, 3, [2, 2, 2, 2, 2]),
+        ];
+        for (num, den, expected) in cases {
+            for (mode, want) in [Trunc, Floor, Ceil, HalfUp, HalfEven]
+                .into_iter()
+                .zip(expected)
+            {
+                assert_eq!(mode.div_i128(num, den), want, "{num}/{den} {mode:?}");
+            }
+        }
+    }
+
     #[test]
     fn test_abs() {
         assert_eq!(Microns(-1).abs(), Microns(1));
@@ -161,4 +605,453 @@ mod tests {
         assert_eq!(Microns(0).abs(), Microns(0));
         assert_eq!(Microns(666).abs(), Microns(666));
     }
+
+    #[test]
+    fn test_assign() {
+        let mut a = Microns(1);
+        a += Microns(2);
+        assert_eq!(a, Microns(3));
+        a -= Microns(5);
+        assert_eq!(a, Microns(-2));
+        a += 0.003;
+        assert_eq!(a, Microns(1));
+        a -= 0.002;
+        assert_eq!(a, Microns(-1));
+        a *= 4.0;
+        assert_eq!(a, Microns(-4));
+        a /= 2.0;
+        assert_eq!(a, Microns(-2));
+        a *= 3;
+        assert_eq!(a, Microns(-6));
+        a /= 4;
+        assert_eq!(a, Microns(-1));
+    }
+
+    #[test]
+    fn test_sum() {
+        let path = [Microns(1), Microns(2), Microns(-4)];
+        assert_eq!(path.iter().sum::<Microns>(), Microns(-1));
+        assert_eq!(path.into_iter().sum::<Microns>(), Microns(-1));
+        assert_eq!(
+            core::iter::empty::<Microns>().sum::<Microns>(),
+            Microns::ZERO
+        );
+        // intermediate totals leave the i32 range but the result doesn't
+        let swings = [Microns::MAX, Microns::MAX, Microns::MIN, Microns::MIN];
+        assert_eq!(swings.iter().sum::<Microns>(), Microns(-2));
+        assert_eq!(Microns::checked_sum([Microns::MAX, Microns(1)]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to sum with overflow")]
+    fn test_sum_overflow() {
+        let _: Microns = [Microns::MAX, Microns::MAX].iter().sum();
+    }
+
+    #[test]
+    fn test_mul_div_i32() {
+        let layer_height = Microns(200);
+        assert_eq!(layer_height * 7, Microns(1400));
+        assert_eq!(7 * layer_height, Microns(1400));
+        assert_eq!(Microns(1_000_000_001) * 2, Microns(2_000_000_002));
+        assert_eq!(Microns(-7) / 2, Microns(-3));
+    }
+
+    #[test]
+    fn test_div_microns() {
+        assert_eq!(Microns(3000) / Microns(200), 15.0);
+        assert_eq!(Microns(-1) / Microns(4), -0.25);
+        assert_eq!(Microns::MAX / Microns::MAX, 1.0);
+        assert!((Microns(1) / Microns::ZERO).is_infinite());
+    }
+
+    #[test]
+    fn test_round_to() {
+        let step = Microns(250);
+        assert_eq!(
+            Microns(1130).round_to(step, RoundingMode::HalfEven),
+            Microns(1250)
+        );
+        assert_eq!(
+            Microns(1125).round_to(step, RoundingMode::HalfEven),
+            Microns(1000)
+        );
+        assert_eq!(
+            Microns(1125).round_to(step, RoundingMode::HalfUp),
+            Microns(1250)
+        );
+        assert_eq!(
+            Microns(-1130).round_to(step, RoundingMode::Trunc),
+            Microns(-1000)
+        );
+        assert_eq!(Microns(-1130).floor_to(step), Microns(-1250));
+        assert_eq!(Microns(-1130).ceil_to(-step), Microns(-1000));
+        assert_eq!(Microns(1000).ceil_to(step), Microns(1000));
+        assert_eq!(Microns::MIN.floor_to(Microns(1)), Microns::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_round_to_overflow() {
+        let _ = Microns::MAX.ceil_to(Microns(1000));
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let (probe, infill) = (Microns(4500), Microns(-1200));
+        assert_eq!(probe.gcd(infill), Microns(300));
+        assert_eq!(probe.lcm(infill), Microns(18000));
+        assert_eq!(Microns(0).gcd(infill), Microns(1200));
+        assert_eq!(Microns(0).lcm(infill), Microns(0));
+        assert_eq!(Microns::MIN.gcd(Microns(6)), Microns(2));
+        assert!(Microns(200_000).is_multiple_of(Microns(-2500)));
+        assert!(!Microns(200_001).is_multiple_of(Microns(2500)));
+        assert!(Microns::MIN.is_multiple_of(Microns::MIN));
+        assert!(Microns(0).is_multiple_of(Microns(0)));
+        assert!(!Microns(1).is_multiple_of(Microns(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_lcm_overflow() {
+        let _ = Microns::MAX.lcm(Microns(2));
+    }
+
+    #[test]
+    fn test_rem() {
+        let pitch = Microns(1250);
+        assert_eq!(Microns(4000) % pitch, Microns(250));
+        assert_eq!(Microns(-4000) % pitch, Microns(-250));
+        assert_eq!(Microns(4000).div_rem(pitch), (3, Microns(250)));
+        assert_eq!(Microns(-4000).div_rem(pitch), (-3, Microns(-250)));
+        assert_eq!(Microns(-4000).div_euclid(pitch), -4);
+        assert_eq!(Microns(-4000).rem_euclid(pitch), Microns(1000));
+        assert_eq!(Microns(4000).rem_euclid(-pitch), Microns(250));
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-Microns(5), Microns(-5));
+        assert_eq!(-Microns(-5), Microns(5));
+        assert_eq!(-Microns::ZERO, Microns::ZERO);
+        assert_eq!(-Microns::MAX, Microns(i32::MIN + 1));
+    }
+
+    #[test]
+    fn test_unit_constants() {
+        let pos = Microns(250);
+        assert_eq!(pos + Microns::MM * 5, Microns(5250));
+        assert_eq!(Microns::CM, Microns::MM * 10);
+        assert_eq!(Microns::M, Microns::CM * 100);
+        assert_eq!(Microns::INCH.mul_ratio(10, 1000), Microns(254));
+        assert_eq!(Microns::MIL * 10, Microns(250));
+        assert_eq!(pos + Microns::EPSILON, Microns(251));
+        assert_eq!(f32::from(Microns::INCH), 25.4);
+    }
+
+    #[test]
+    fn test_const() {
+        const TRAVEL: Microns = Microns::new(220_000).sub(Microns::MM.abs());
+        const LIMITS: [Microns; 2] = [TRAVEL.min(Microns::ZERO), TRAVEL.max(Microns::ZERO)];
+        const HOME: Microns = Microns::from_um(-5_000).add(Microns::EPSILON);
+        const CLAMPED: Microns = HOME.clamp(LIMITS[0], LIMITS[1]);
+        const OFFSET: Option<Microns> = Microns::MAX.checked_add(HOME);
+        assert_eq!(TRAVEL, Microns(219_000));
+        assert_eq!(LIMITS, [Microns(0), Microns(219_000)]);
+        assert_eq!(HOME, Microns(-4_999));
+        assert_eq!(CLAMPED, Microns(0));
+        assert_eq!(OFFSET, Some(Microns(i32::MAX - 4_999)));
+        assert_eq!(Microns(2).add(Microns(3)), Microns(2) + Microns(3));
+    }
+
+    #[test]
+    fn test_min_max_clamp() {
+        let (lo, hi) = (Microns(-100), Microns(200_000));
+        assert_eq!(Microns(5).min(Microns(-5)), Microns(-5));
+        assert_eq!(Microns(5).max(Microns(-5)), Microns(5));
+        assert_eq!(Microns(300_000).clamp(lo, hi), hi);
+        assert_eq!(Microns(-300).clamp(lo, hi), lo);
+        assert_eq!(Microns(42).clamp(lo, hi), Microns(42));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_clamp_inverted() {
+        let _ = Microns(0).clamp(Microns(1), Microns(-1));
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(Microns(0).midpoint(Microns(10)), Microns(5));
+        assert_eq!(Microns(-3).midpoint(Microns(0)), Microns(-1));
+        assert_eq!(Microns(3).midpoint(Microns(0)), Microns(1));
+        assert_eq!(Microns::MAX.midpoint(Microns::MAX), Microns::MAX);
+        assert_eq!(Microns::MIN.midpoint(Microns::MAX), Microns(0));
+        assert_eq!(
+            Microns::MAX.midpoint(Microns(i32::MAX - 2)),
+            Microns(i32::MAX - 1)
+        );
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        let (nominal, tol) = (Microns(10_000), Microns(5));
+        assert!(Microns(10_004).abs_diff(nominal) < tol);
+        assert!(Microns(9_994).abs_diff(nominal) > tol);
+        assert_eq!(Microns(-3).abs_diff(Microns(4)), Microns(7));
+        assert_eq!(Microns::MAX.abs_diff(Microns(0)), Microns::MAX);
+        assert_eq!(Microns::MIN.abs_diff(Microns(-1)), Microns::MAX);
+        assert_eq!(Microns::MIN.abs_diff(Microns::MAX), Microns::MAX);
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(Microns(-7).signum(), -1);
+        assert_eq!(Microns(0).signum(), 0);
+        assert_eq!(Microns(7).signum(), 1);
+        assert!(Microns(1).is_positive() && !Microns(1).is_negative());
+        assert!(Microns(-1).is_negative() && !Microns(-1).is_positive());
+        assert!(
+            Microns::ZERO.is_zero() && !Microns::ZERO.is_positive() && !Microns::ZERO.is_negative()
+        );
+    }
+
+    #[test]
+    fn test_hypot() {
+        assert_eq!(Microns(3000).hypot(Microns(-4000)), Microns(5000));
+        assert_eq!(Microns(1).hypot(Microns(1)), Microns(1));
+        assert_eq!(Microns(2).hypot3(Microns(3), Microns(6)), Microns(7));
+        assert_eq!(Microns(-5).hypot(Microns(-12)), Microns(13));
+        let near_max = Microns(i32::MAX / 2);
+        assert_eq!(
+            near_max.hypot3(near_max, Microns(0)),
+            Microns(1_518_500_248)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_hypot_overflow() {
+        let _ = Microns::MAX.hypot(Microns::MAX);
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(Microns(-12).as_micrometers(), -12);
+        assert_eq!(Microns::MAX.to_i64() * 2, 4_294_967_294);
+        assert_eq!(Microns::MIN.to_i128() * Microns::MIN.to_i128(), 1 << 62);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(Microns(1).checked_add(Microns(2)), Some(Microns(3)));
+        assert_eq!(Microns::MAX.checked_add(Microns(1)), None);
+        assert_eq!(Microns(1).checked_sub(Microns(2)), Some(Microns(-1)));
+        assert_eq!(Microns::MIN.checked_sub(Microns(1)), None);
+        assert_eq!(Microns(3).checked_mul_i32(-2), Some(Microns(-6)));
+        assert_eq!(Microns::MAX.checked_mul_i32(2), None);
+        assert_eq!(Microns(7).checked_div_i32(2), Some(Microns(3)));
+        assert_eq!(Microns(7).checked_div_i32(0), None);
+        assert_eq!(Microns::MIN.checked_div_i32(-1), None);
+        assert_eq!(Microns(5).checked_neg(), Some(Microns(-5)));
+        assert_eq!(Microns::MIN.checked_neg(), None);
+    }
+
+    #[test]
+    fn test_saturating() {
+        assert_eq!(Microns(1).saturating_add(Microns(2)), Microns(3));
+        assert_eq!(Microns::MAX.saturating_add(Microns(1)), Microns::MAX);
+        assert_eq!(Microns::MIN.saturating_sub(Microns(1)), Microns::MIN);
+        assert_eq!(Microns(-3).saturating_mul_i32(i32::MAX), Microns::MIN);
+        assert_eq!(Microns::saturating_from_f32(0.002), Microns(2));
+        assert_eq!(Microns::saturating_from_f32(1e9), Microns::MAX);
+        assert_eq!(
+            Microns::saturating_from_f32(f32::NEG_INFINITY),
+            Microns::MIN
+        );
+        assert_eq!(Microns::saturating_from_f32(f32::NAN), Microns::ZERO);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        assert_eq!(Microns::MAX.wrapping_add(Microns(1)), Microns::MIN);
+        assert_eq!(Microns::MIN.wrapping_sub(Microns(1)), Microns::MAX);
+        assert_eq!(Microns::MIN.wrapping_neg(), Microns::MIN);
+        assert_eq!(Microns(4).wrapping_neg(), Microns(-4));
+        assert_eq!(Microns(1).overflowing_add(Microns(2)), (Microns(3), false));
+        assert_eq!(
+            Microns::MAX.overflowing_add(Microns(2)),
+            (Microns(i32::MIN + 1), true)
+        );
+        assert_eq!(
+            Microns::MIN.overflowing_sub(Microns(1)),
+            (Microns::MAX, true)
+        );
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let nominal = Microns(10_000);
+        assert!(nominal.approx_eq(Microns(10_005), Microns(5)));
+        assert!(nominal.approx_eq(Microns(9_995), Microns(-5)));
+        assert!(!nominal.approx_eq(Microns(10_006), Microns(5)));
+        assert!(!Microns::MIN.approx_eq(Microns::MAX, Microns::MAX));
+        assert!(nominal.within(Microns(9_990)..=Microns(10_000)));
+        assert!(!nominal.within(Microns(9_990)..Microns(10_000)));
+        assert!(nominal.within(..));
+        assert!(nominal.within(Microns::MM..));
+    }
+
+    #[test]
+    fn test_cmp_float() {
+        let pos = Microns(200_500);
+        assert!(pos > 200.0);
+        assert!(pos < 200.6f32);
+        assert!(200.0 < pos);
+        assert!(pos == 200.5);
+        assert!(pos == 200.5005f64);
+        assert!(200.5f32 == pos);
+        assert!(pos != 200.4);
+        assert!(pos < f32::INFINITY && pos < 1e30f64);
+        assert!(pos > f32::NEG_INFINITY && Microns::MIN > -1e30f64);
+        assert!(!pos.eq(&f32::NAN));
+        assert_eq!(pos.partial_cmp(&f64::NAN), None);
+        assert!(!pos.lt(&f64::NAN) && !pos.ge(&f64::NAN));
+    }
+
+    #[test]
+    fn test_try_f32_ops() {
+        let a = Microns(1000);
+        assert_eq!(a.try_add_f32(0.5), Ok(Microns(1500)));
+        assert_eq!(a.try_sub_f32(1.5), Ok(Microns(-500)));
+        assert_eq!(a.try_mul_f32(-2.5), Ok(Microns(-2500)));
+        assert_eq!(a.try_div_f32(4.0), Ok(Microns(250)));
+        assert_eq!(a.try_add_f32(f32::NAN), Err(ConversionError::NaN));
+        assert_eq!(
+            Microns::MAX.try_add_f32(0.001),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Microns::MIN.try_sub_f32(0.001),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(
+            Microns::MIN.try_mul_f32(-1.0),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(a.try_div_f32(0.0), Err(ConversionError::Overflow));
+        assert_eq!(a.try_div_f32(-0.0), Err(ConversionError::Underflow));
+        assert_eq!(Microns::ZERO.try_div_f32(0.0), Err(ConversionError::NaN));
+    }
+
+    #[test]
+    fn test_strict() {
+        assert_eq!(Microns(1).strict_add(Microns(2)), Microns(3));
+        assert_eq!(Microns(1).strict_sub(Microns(2)), Microns(-1));
+        assert_eq!(Microns(3).strict_mul_i32(3), Microns(9));
+        assert_eq!(Microns(9).strict_div_i32(3), Microns(3));
+        assert_eq!(Microns(9).strict_neg(), Microns(-9));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn test_strict_add_overflow() {
+        let _ = Microns::MAX.strict_add(Microns(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to negate with overflow")]
+    fn test_strict_neg_overflow() {
+        let _ = Microns::MIN.strict_neg();
+    }
+
+    #[test]
+    fn test_f32_round_trip() {
+        let check = |m: i32| {
+            let m = Microns(m);
+            assert_eq!(Microns::from(m.to_nearest_f32()), m, "{m:?}");
+            assert!(m.is_exactly_representable_f32());
+        };
+        (-100_000..=100_000).for_each(check);
+        (-16_384_000..=16_384_000).step_by(997).for_each(check);
+        (16_383_000..=16_384_000).for_each(check);
+        (-16_384_000..=-16_383_000).for_each(check);
+        assert_eq!(Microns::from(f32::from(Microns(251))), Microns(250));
+        assert_eq!(Microns(251).to_nearest_f32(), 0.251f32.next_up());
+        assert_eq!(Microns(250).to_nearest_f32(), 0.25);
+        assert!(!Microns(16_384_001).is_exactly_representable_f32());
+        assert!(!Microns::MAX.is_exactly_representable_f32());
+        assert!(Microns(1 << 30).is_exactly_representable_f32());
+    }
+
+    #[test]
+    fn test_try_from_f32() {
+        assert_eq!(Microns::try_from_f32(0.001), Ok(Microns(1)));
+        assert_eq!(Microns::try_from_f32(f32::NAN), Err(ConversionError::NaN));
+        assert_eq!(
+            Microns::try_from_f32(f32::INFINITY),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Microns::try_from_f32(-1e10),
+            Err(ConversionError::Underflow)
+        );
+        assert!(!works(3e6));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_from_f32_out_of_range() {
+        let _ = Microns::from(f32::NAN);
+    }
+
+    #[test]
+    fn test_from_f32_with() {
+        use RoundingMode::*;
+        let cases = [
+            (0.0015, [1, 1, 2, 2, 2]),
+            (0.0025, [2, 2, 3, 3, 2]),
+            (-0.0015, [-1, -2, -1, -2, -2]),
+            (0.0014, [1, 1, 2, 1, 1]),
+        ];
+        for (val, expected) in cases {
+            for (mode, want) in [Trunc, Floor, Ceil, HalfUp, HalfEven]
+                .into_iter()
+                .zip(expected)
+            {
+                assert_eq!(
+                    Microns::from_f32_with(val, mode),
+                    Microns(want),
+                    "{val} {mode:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_f32_rounded() {
+        assert_eq!(Microns::from(0.9999999), Microns(999));
+        assert_eq!(Microns::from_f32_rounded(0.9999999), Microns(1000));
+        assert_eq!(Microns::from_f32_rounded(-0.9999999), Microns(-1000));
+        assert_eq!(Microns::from_f32_rounded(0.0025), Microns(2));
+    }
+
+    #[test]
+    fn test_conversion_monotonic() {
+        let mut vals: Vec<f32> = (-200_000..200_000).map(|i| i as f32 * 0.000_05).collect();
+        // every float right around a few whole-millimeter boundaries as well
+        for mm in [-10.0f32, -1.0, 1.0, 10.0] {
+            let bits = mm.to_bits();
+            vals.extend((bits - 1_000..bits + 1_000).map(f32::from_bits));
+        }
+        vals.sort_by(f32::total_cmp);
+        for pair in vals.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert!(Microns::from(a) <= Microns::from(b), "{a} {b}");
+            assert!(
+                Microns::from_f32_rounded(a) <= Microns::from_f32_rounded(b),
+                "{a} {b}"
+            );
+        }
+    }
 }
@@ -0,0 +1,117 @@
+//! Building `lyon` paths from Microns polygons, and tessellating them back
+//! into Microns meshes.
+//!
+//! lyon works in `f32` millimeters. Tessellated vertices are rounded to
+//! the nearest micrometer, so within ±8 m, where f32 still resolves well
+//! under a micrometer, a mesh shares its vertices exactly with the polygons
+//! it came from.
+//!
+//! ```
+//! use microns::{lyon, Microns, Point2};
+//!
+//! let square = [(0, 0), (1000, 0), (1000, 1000), (0, 1000)]
+//!     .map(|(x, y)| Point2::new(Microns(x), Microns(y)));
+//! let mesh = lyon::fill(&lyon::to_path([square]), Microns(10)).unwrap();
+//! assert_eq!(mesh.indices.len(), 6);
+//! assert!(mesh.vertices.iter().all(|v| square.contains(v)));
+//! ```
+use crate::{ConversionError, Microns, Point2, RoundingMode};
+use lyon_tessellation::math::{point, Point};
+use lyon_tessellation::path::Path;
+use lyon_tessellation::{
+    BuffersBuilder, FillOptions, FillTessellator, FillVertex, TessellationError, VertexBuffers,
+};
+
+/// In millimeters.
+impl From<Point2> for Point {
+    fn from(p: Point2) -> Self {
+        point(f32::from(p.x), f32::from(p.y))
+    }
+}
+
+/// From millimeters, rounding each coordinate to the nearest micrometer.
+impl TryFrom<Point> for Point2 {
+    type Error = ConversionError;
+    fn try_from(p: Point) -> Result<Self, ConversionError> {
+        let um = |mm| Microns::try_from_f32_with(mm, RoundingMode::HalfEven);
+        Ok(Point2::new(um(p.x)?, um(p.y)?))
+    }
+}
+
+/// A path with one closed subpath per polygon. Polygons with fewer than
+/// two points are skipped.
+pub fn to_path<P: AsRef<[Point2]>>(polygons: impl IntoIterator<Item = P>) -> Path {
+    let mut builder = Path::builder();
+    for polygon in polygons {
+        if let [first, rest @ ..] = polygon.as_ref() {
+            if rest.is_empty() {
+                continue;
+            }
+            builder.begin((*first).into());
+            rest.iter().for_each(|&p| {
+                builder.line_to(p.into());
+            });
+            builder.close();
+        }
+    }
+    builder.build()
+}
+
+/// Triangulates the inside of `path` by the non-zero rule, with curves
+/// flattened to within `tolerance`. Vertices are rounded like
+/// `TryFrom<Point>`, and those past the Microns range saturate.
+pub fn fill(
+    path: &Path,
+    tolerance: Microns,
+) -> Result<VertexBuffers<Point2, u32>, TessellationError> {
+    let options = FillOptions::non_zero().with_tolerance(f32::from(tolerance));
+    let mut mesh = VertexBuffers::new();
+    FillTessellator::new().tessellate_path(
+        path,
+        &options,
+        &mut BuffersBuilder::new(&mut mesh, |v: FillVertex| {
+            let p = v.position();
+            let um = |mm| match Microns::try_from_f32_with(mm, RoundingMode::HalfEven) {
+                Ok(m) => m,
+                Err(ConversionError::NaN) => Microns::ZERO,
+                Err(ConversionError::Overflow) => Microns::MAX,
+                Err(ConversionError::Underflow) => Microns::MIN,
+            };
+            Point2::new(um(p.x), um(p.y))
+        }),
+    )?;
+    Ok(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lyon() {
+        let p = Point2::new(Microns(-1_250), Microns(3));
+        assert_eq!(Point::from(p), point(-1.25, 0.003));
+        assert_eq!(Point2::try_from(Point::from(p)), Ok(p));
+        let triangle =
+            [(0, 0), (2_000, 0), (0, 1_000)].map(|(x, y)| Point2::new(Microns(x), Microns(y)));
+        let path = to_path([&triangle[..], &triangle[..1]]);
+        assert_eq!(path.iter().count(), 4);
+        let mesh = fill(&path, Microns(1)).unwrap();
+        assert_eq!(mesh.indices.len(), 3);
+        let mut vertices = mesh.vertices.clone();
+        vertices.sort_by_key(|v| (v.x, v.y));
+        assert_eq!(vertices, [triangle[0], triangle[2], triangle[1]]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for um in (-200_000..200_000).chain([5, 1_009, -1_009, 8_191_999, -8_191_999]) {
+            let p = Point2::new(Microns(um), Microns(-um));
+            assert_eq!(Point2::try_from(Point::from(p)), Ok(p), "{um}");
+        }
+        let square = [(0, 0), (1_005, 0), (1_005, 1_009), (0, 1_009)]
+            .map(|(x, y)| Point2::new(Microns(x), Microns(y)));
+        let mesh = fill(&to_path([square]), Microns(10)).unwrap();
+        assert!(mesh.vertices.iter().all(|v| square.contains(v)));
+    }
+}
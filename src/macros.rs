@@ -0,0 +1,90 @@
+//! Literal macros for Microns.
+use crate::parse::{scan_decimal, ScanError};
+use crate::Microns;
+
+/// Builds Microns from a millimeter literal at compile time, exactly:
+/// `mm!(1.250)` is `Microns(1250)`. Literals with sub-micron digits or
+/// outside the Microns range fail to compile.
+///
+/// ```
+/// use microns::{mm, Microns};
+/// const LAYER: Microns = mm!(0.2);
+/// assert_eq!(LAYER, Microns(200));
+/// assert_eq!(mm!(-1_000.5), Microns(-1_000_500));
+/// ```
+///
+/// ```compile_fail
+/// let _ = microns::mm!(0.0005);
+/// ```
+#[macro_export]
+macro_rules! mm {
+    ($val:literal) => {
+        const { $crate::__mm_literal(stringify!($val)) }
+    };
+}
+
+/// Builds Microns from a micrometer count at compile time: `um!(750)` is
+/// `Microns(750)`.
+///
+/// ```
+/// use microns::{um, Microns};
+/// assert_eq!(um!(750), Microns(750));
+/// ```
+#[macro_export]
+macro_rules! um {
+    ($val:expr) => {
+        const { $crate::Microns::from_um($val) }
+    };
+}
+
+#[doc(hidden)]
+pub const fn __mm_literal(lit: &str) -> Microns {
+    let bytes = lit.as_bytes();
+    let (dec, len) = match scan_decimal(bytes, true) {
+        Ok(scanned) => scanned,
+        Err(ScanError::NoDigits) => panic!("mm! expects a decimal literal"),
+        Err(ScanError::TooManyDigits) => panic!("mm! literal has too many digits"),
+    };
+    if len != bytes.len() {
+        panic!("mm! expects a plain decimal literal without a suffix or exponent");
+    }
+    match dec.to_um(1000, 1) {
+        Some((um, true)) if um >= i32::MIN as i128 && um <= i32::MAX as i128 => Microns(um as i32),
+        Some((_, false)) => panic!("mm! literal has more than three decimal places"),
+        _ => panic!("mm! literal is out of range for Microns"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mm() {
+        assert_eq!(mm!(1.250), Microns(1250));
+        assert_eq!(mm!(-0.001), Microns(-1));
+        assert_eq!(mm!(12), Microns(12_000));
+        assert_eq!(mm!(0.1000), Microns(100));
+        assert_eq!(mm!(2147483.647), Microns::MAX);
+        assert_eq!(mm!(-2147483.648), Microns::MIN);
+    }
+
+    #[test]
+    fn test_um() {
+        const HALF: i32 = 375;
+        assert_eq!(um!(750), Microns(750));
+        assert_eq!(um!(-HALF * 2), Microns(-750));
+    }
+
+    #[test]
+    #[should_panic(expected = "suffix or exponent")]
+    fn test_mm_literal_suffix() {
+        let _ = __mm_literal("1.5f32");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_mm_literal_range() {
+        let _ = __mm_literal("2147483.648");
+    }
+}
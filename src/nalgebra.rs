@@ -0,0 +1,95 @@
+//! Conversions to and from `nalgebra` points and vectors.
+//!
+//! Integer conversions are exact micrometers; `f64` ones are millimeters,
+//! truncated toward zero on the way back, so a transform can run in floats
+//! and land on whole microns:
+//!
+//! ```
+//! use microns::{Microns, Point3};
+//! use nalgebra::{Isometry3, Vector3};
+//!
+//! let p = Point3::new(Microns(1000), Microns::ZERO, Microns::ZERO);
+//! let shift = Isometry3::translation(0.0, 2.5, 0.0);
+//! let moved = Point3::try_from(shift * nalgebra::Point3::<f64>::from(p)).unwrap();
+//! assert_eq!(moved, Point3::new(Microns(1000), Microns(2500), Microns::ZERO));
+//! ```
+//!
+//! `Microns` meets nalgebra's `Scalar` bounds, so `nalgebra::Point3<Microns>`
+//! works as well and converts to `Point3` for free.
+use crate::{ConversionError, Microns, Point2, Point3, Vec2, Vec3};
+
+macro_rules! impl_nalgebra {
+    ($($ours:ident, $theirs:ident { $($c:ident),* };)*) => {$(
+        impl From<$ours> for nalgebra::$theirs<i32> {
+            fn from(p: $ours) -> Self {
+                nalgebra::$theirs::new($(p.$c.0),*)
+            }
+        }
+
+        impl From<nalgebra::$theirs<i32>> for $ours {
+            fn from(p: nalgebra::$theirs<i32>) -> Self {
+                $ours { $($c: Microns(p.$c)),* }
+            }
+        }
+
+        impl From<$ours> for nalgebra::$theirs<Microns> {
+            fn from(p: $ours) -> Self {
+                nalgebra::$theirs::new($(p.$c),*)
+            }
+        }
+
+        impl From<nalgebra::$theirs<Microns>> for $ours {
+            fn from(p: nalgebra::$theirs<Microns>) -> Self {
+                $ours { $($c: p.$c),* }
+            }
+        }
+
+        /// In millimeters.
+        impl From<$ours> for nalgebra::$theirs<f64> {
+            fn from(p: $ours) -> Self {
+                nalgebra::$theirs::new($(f64::from(p.$c)),*)
+            }
+        }
+
+        /// From millimeters, truncating each coordinate toward zero.
+        impl TryFrom<nalgebra::$theirs<f64>> for $ours {
+            type Error = ConversionError;
+            fn try_from(p: nalgebra::$theirs<f64>) -> Result<Self, ConversionError> {
+                Ok($ours { $($c: Microns::try_from(p.$c)?),* })
+            }
+        }
+    )*};
+}
+impl_nalgebra! {
+    Point2, Point2 { x, y };
+    Point3, Point3 { x, y, z };
+    Vec2, Vector2 { x, y };
+    Vec3, Vector3 { x, y, z };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nalgebra_conversions() {
+        let p = Point3::new(Microns(-1), Microns(2), Microns::MAX);
+        let exact = nalgebra::Point3::<i32>::from(p);
+        assert_eq!(exact, nalgebra::Point3::new(-1, 2, i32::MAX));
+        assert_eq!(Point3::from(exact), p);
+        assert_eq!(Point3::from(nalgebra::Point3::<Microns>::from(p)), p);
+        let v = Vec2::new(Microns(1500), Microns(-250));
+        let mm = nalgebra::Vector2::<f64>::from(v);
+        assert_eq!(mm, nalgebra::Vector2::new(1.5, -0.25));
+        assert_eq!(
+            Vec2::try_from(mm * 2.0),
+            Ok(Vec2::new(Microns(3000), Microns(-500)))
+        );
+        assert_eq!(
+            Vec3::try_from(nalgebra::Vector3::new(0.0, f64::NAN, 0.0)),
+            Err(ConversionError::NaN)
+        );
+        let sum = nalgebra::Vector3::<Microns>::from(Vec3::new(Microns(1), Microns(2), Microns(3)));
+        assert_eq!(sum.x, Microns(1));
+    }
+}
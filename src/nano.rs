@@ -0,0 +1,187 @@
+//! Nanometer resolution in 64 bits.
+//!
+//! `Nanons` covers about ±9.2 million km at 1 nm, so both sub-micron
+//! metrology and very long cumulative travel fit. Widening from Microns is
+//! lossless; narrowing back truncates toward zero like `Microns::from_nm`.
+use crate::{float, ConversionError, Microns};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Nanons holds a length in nanometers.
+pub struct Nanons(pub i64);
+
+impl Nanons {
+    pub const ZERO: Nanons = Nanons(0);
+    pub const MIN: Nanons = Nanons(i64::MIN);
+    pub const MAX: Nanons = Nanons(i64::MAX);
+    pub const UM: Nanons = Nanons(1000);
+    pub const MM: Nanons = Nanons(1_000_000);
+
+    pub const fn new(nm: i64) -> Self {
+        Nanons(nm)
+    }
+
+    /// Widens Microns losslessly; usable in const contexts.
+    pub const fn from_microns(m: Microns) -> Self {
+        Nanons(m.0 as i64 * 1000)
+    }
+
+    /// Converts millimeters, truncating toward zero.
+    pub fn try_from_f64(mm: f64) -> Result<Self, ConversionError> {
+        if mm.is_nan() {
+            return Err(ConversionError::NaN);
+        }
+        let nm = float::trunc_f64(mm * 1e6);
+        if nm >= i64::MAX as f64 {
+            return Err(ConversionError::Overflow);
+        }
+        if nm < i64::MIN as f64 {
+            return Err(ConversionError::Underflow);
+        }
+        Ok(Nanons(nm as i64))
+    }
+
+    pub const fn abs(self) -> Self {
+        Nanons(self.0.abs())
+    }
+
+    pub const fn checked_add(self, rhs: Nanons) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(v) => Some(Nanons(v)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: Nanons) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(v) => Some(Nanons(v)),
+            None => None,
+        }
+    }
+}
+
+impl From<Microns> for Nanons {
+    fn from(m: Microns) -> Self {
+        Nanons::from_microns(m)
+    }
+}
+
+/// Truncates to whole micrometers; fails if that is out of range.
+impl TryFrom<Nanons> for Microns {
+    type Error = ConversionError;
+    fn try_from(n: Nanons) -> Result<Self, ConversionError> {
+        let um = n.0 / 1000;
+        i32::try_from(um).map(Microns).map_err(|_| {
+            if um < 0 {
+                ConversionError::Underflow
+            } else {
+                ConversionError::Overflow
+            }
+        })
+    }
+}
+
+/// Converts to millimeters.
+impl From<Nanons> for f64 {
+    fn from(other: Nanons) -> Self {
+        other.0 as f64 / 1e6
+    }
+}
+
+impl Add for Nanons {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Nanons(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Nanons {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Nanons(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Nanons {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Nanons(-self.0)
+    }
+}
+
+impl Mul<i64> for Nanons {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self {
+        Nanons(self.0 * rhs)
+    }
+}
+
+impl Div<i64> for Nanons {
+    type Output = Self;
+    fn div(self, rhs: i64) -> Self {
+        Nanons(self.0 / rhs)
+    }
+}
+
+impl AddAssign for Nanons {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Nanons {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for Nanons {
+    fn sum<I: Iterator<Item = Nanons>>(iter: I) -> Self {
+        iter.fold(Nanons::ZERO, |acc, n| {
+            acc.checked_add(n).expect("attempt to sum with overflow")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nanons_conversions() {
+        assert_eq!(Nanons::from(Microns(-1250)), Nanons(-1_250_000));
+        assert_eq!(Nanons::from(Microns::MAX).0, i32::MAX as i64 * 1000);
+        assert_eq!(Microns::try_from(Nanons(1999)), Ok(Microns(1)));
+        assert_eq!(Microns::try_from(Nanons(-1999)), Ok(Microns(-1)));
+        assert_eq!(
+            Microns::try_from(Nanons::from(Microns::MIN)),
+            Ok(Microns::MIN)
+        );
+        assert_eq!(
+            Microns::try_from(Nanons::MAX),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(
+            Microns::try_from(Nanons::MIN),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(Nanons::try_from_f64(0.0005), Ok(Nanons(500)));
+        assert_eq!(Nanons::try_from_f64(-1e20), Err(ConversionError::Underflow));
+        assert_eq!(Nanons::try_from_f64(f64::NAN), Err(ConversionError::NaN));
+        assert_eq!(f64::from(Nanons(2_500_000)), 2.5);
+    }
+
+    #[test]
+    fn test_nanons_ops() {
+        let travel: Nanons = core::iter::repeat_n(Nanons::from(Microns::MAX), 4).sum();
+        assert!(Microns::try_from(travel).is_err());
+        assert_eq!(travel / 4, Nanons::from(Microns::MAX));
+        assert_eq!(Nanons::UM * 3 - Nanons(500), Nanons(2500));
+        assert_eq!((-Nanons::MM).abs(), Nanons::MM);
+    }
+}
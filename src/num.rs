@@ -0,0 +1,158 @@
+//! `num-traits` implementations, for generic numeric code.
+//!
+//! Integers are micrometer counts and floats are millimeters, as with the
+//! `From` impls, so `FromPrimitive::from_i64(5)` is 5 µm while `from_f64(5.0)`
+//! is 5 mm. Lengths have no `One`, and so no `Num` or `Signed`: a length times
+//! a length is an area. The dimensionless `Percent` is the ratio type and does
+//! have `One`, which is 100%.
+use crate::{FixedDecimal, Percent};
+use num_traits::{
+    Bounded, CheckedAdd, CheckedNeg, CheckedSub, FromPrimitive, One, ToPrimitive, Zero,
+};
+
+macro_rules! impl_num {
+    ($($t:ty),*) => {$(
+        impl<const SCALE: u32> Zero for FixedDecimal<SCALE, $t> {
+            fn zero() -> Self {
+                Self::ZERO
+            }
+
+            fn is_zero(&self) -> bool {
+                self.0 == 0
+            }
+        }
+
+        impl<const SCALE: u32> Bounded for FixedDecimal<SCALE, $t> {
+            fn min_value() -> Self {
+                Self::MIN
+            }
+
+            fn max_value() -> Self {
+                Self::MAX
+            }
+        }
+
+        impl<const SCALE: u32> CheckedAdd for FixedDecimal<SCALE, $t> {
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                FixedDecimal::<SCALE, $t>::checked_add(*self, *rhs)
+            }
+        }
+
+        impl<const SCALE: u32> CheckedSub for FixedDecimal<SCALE, $t> {
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                FixedDecimal::<SCALE, $t>::checked_sub(*self, *rhs)
+            }
+        }
+
+        impl<const SCALE: u32> CheckedNeg for FixedDecimal<SCALE, $t> {
+            fn checked_neg(&self) -> Option<Self> {
+                FixedDecimal::<SCALE, $t>::checked_neg(*self)
+            }
+        }
+
+        impl<const SCALE: u32> ToPrimitive for FixedDecimal<SCALE, $t> {
+            fn to_i64(&self) -> Option<i64> {
+                self.0.to_i64()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                self.0.to_u64()
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                Some(self.0 as i128)
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                self.0.to_u128()
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                Some(f32::from(*self))
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                Some(f64::from(*self))
+            }
+        }
+
+        /// `None` when the value is out of range or NaN; floats truncate
+        /// toward zero.
+        impl<const SCALE: u32> FromPrimitive for FixedDecimal<SCALE, $t> {
+            fn from_i64(um: i64) -> Option<Self> {
+                <$t>::try_from(um).ok().map(FixedDecimal)
+            }
+
+            fn from_u64(um: u64) -> Option<Self> {
+                <$t>::try_from(um).ok().map(FixedDecimal)
+            }
+
+            fn from_i128(um: i128) -> Option<Self> {
+                <$t>::try_from(um).ok().map(FixedDecimal)
+            }
+
+            fn from_u128(um: u128) -> Option<Self> {
+                <$t>::try_from(um).ok().map(FixedDecimal)
+            }
+
+            fn from_f32(mm: f32) -> Option<Self> {
+                Self::try_from_f32(mm).ok()
+            }
+
+            fn from_f64(mm: f64) -> Option<Self> {
+                Self::try_from(mm).ok()
+            }
+        }
+    )*};
+}
+impl_num!(i32, i64);
+
+impl Zero for Percent {
+    fn zero() -> Self {
+        Percent::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl One for Percent {
+    fn one() -> Self {
+        Percent::FULL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Microns, Microns64};
+
+    fn total<T: Zero + CheckedAdd + Copy>(items: &[T]) -> Option<T> {
+        items
+            .iter()
+            .try_fold(T::zero(), |acc, x| acc.checked_add(x))
+    }
+
+    #[test]
+    fn test_num_traits() {
+        assert_eq!(total(&[Microns(1), Microns(2)]), Some(Microns(3)));
+        assert_eq!(total(&[Microns::MAX, Microns(1)]), None);
+        assert_eq!(<Microns as Bounded>::min_value(), Microns::MIN);
+        assert_eq!(CheckedNeg::checked_neg(&Microns::MIN), None);
+        assert!(Microns64::zero().is_zero());
+        assert_eq!(Microns::from_i64(5), Some(Microns(5)));
+        assert_eq!(Microns::from_u64(u64::MAX), None);
+        assert_eq!(Microns::from_f64(1.25), Some(Microns(1250)));
+        assert_eq!(Microns::from_f32(f32::NAN), None);
+        assert_eq!(ToPrimitive::to_i64(&Microns(-1250)), Some(-1250));
+        assert_eq!(Microns(-1250).to_u64(), None);
+        assert_eq!(Microns(-1250).to_f64(), Some(-1.25));
+        assert_eq!(
+            Percent::from_whole(50) * Percent::from_whole(50),
+            Percent(2_500)
+        );
+        assert_eq!(Percent::one() * Percent(9_550), Percent(9_550));
+        assert!(Percent::zero().is_zero());
+    }
+}
@@ -0,0 +1,81 @@
+//! Wrapper types that pick an overflow policy for the arithmetic operators,
+//! so switching policy is a matter of changing a type alias:
+//!
+//! ```
+//! use microns::{Checked, Microns};
+//! type Pos = Checked<Microns>;
+//! let p = Pos::from(Microns(5)) + Pos::from(Microns(10));
+//! assert_eq!(p.0, Microns(15));
+//! ```
+//!
+//! Both wrap any `FixedDecimal` and provide `+`, `-`, `*` by the backing
+//! integer, unary `-`, and their assigning forms.
+
+/// Operators panic on overflow, in debug and release builds alike.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checked<T>(pub T);
+
+/// Operators clamp to `MIN`/`MAX` of the wrapped type instead of
+/// overflowing.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Saturating<T>(pub T);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FixedDecimal, Microns, Microns64};
+
+    #[test]
+    fn test_checked() {
+        let a = Checked(Microns(1));
+        let b = Checked(Microns(2));
+        assert_eq!(a + b, Checked(Microns(3)));
+        assert_eq!(a - b, Checked(Microns(-1)));
+        assert_eq!(b * 4, Checked(Microns(8)));
+        assert_eq!(-b, Checked(Microns(-2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to add with overflow")]
+    fn test_checked_overflow() {
+        let _ = Checked(Microns::MAX) + Checked(Microns(1));
+    }
+
+    #[test]
+    fn test_saturating() {
+        let max = Saturating(Microns::MAX);
+        let min = Saturating(Microns::MIN);
+        assert_eq!(max + Saturating(Microns(1)), max);
+        assert_eq!(min - Saturating(Microns(1)), min);
+        assert_eq!(max * -2, min);
+        assert_eq!(-min, max);
+        assert_eq!(
+            Saturating(Microns(2)) + Saturating(Microns(3)),
+            Saturating(Microns(5))
+        );
+    }
+
+    #[test]
+    fn test_assign_and_other_widths() {
+        let mut c = Checked(Microns64::new(5_000_000_000));
+        c += Checked(Microns64::new(1));
+        c -= Checked(Microns64::new(2));
+        c *= 2;
+        assert_eq!(c, Checked(Microns64::new(9_999_999_998)));
+        let mut s = Saturating::from(Microns64::MAX);
+        s += Saturating(Microns64::new(1));
+        assert_eq!(s, Saturating(Microns64::MAX));
+        s *= -1;
+        s -= Saturating(Microns64::MM);
+        assert_eq!(s, Saturating(Microns64::MIN));
+        let nm = Checked::from(FixedDecimal::<6, i32>::MM) * 2;
+        assert_eq!(-nm, Checked(FixedDecimal::<6, i32>(-2_000_000)));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn test_checked_mul_assign_overflow() {
+        let mut c = Checked(Microns64::MAX);
+        c *= 2;
+    }
+}
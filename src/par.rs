@@ -0,0 +1,154 @@
+//! Parallel versions of the `convert` slice operations, using `rayon`.
+//!
+//! Slices are split into fixed-size blocks and each block runs the same
+//! loop as its `convert` counterpart, so results are identical. An error
+//! still reports the lowest failing index, but other blocks may have been
+//! processed past it.
+//!
+//! ```
+//! use microns::{par, Microns};
+//!
+//! let mm: Vec<f32> = (0..100_000).map(|i| i as f32 / 1000.0).collect();
+//! let mut xs = par::from_f32_slice(&mm).unwrap();
+//! par::offset(&mut xs, Microns(5)).unwrap();
+//! assert_eq!(par::min_max(&xs), Some((Microns(5), Microns(100_004))));
+//! ```
+use crate::convert::{self, as_um_slice, as_um_slice_mut};
+use crate::{ConversionError, Microns};
+use rayon::prelude::*;
+use std::{vec, vec::Vec};
+
+/// Elements per task; large enough that scheduling is noise next to the work.
+const BLOCK: usize = 1 << 14;
+
+/// The lowest-indexed error from per-block results, with block offsets added.
+fn first_error(
+    results: impl ParallelIterator<Item = (usize, Result<(), (usize, ConversionError)>)>,
+) -> Result<(), (usize, ConversionError)> {
+    results
+        .filter_map(|(block, r)| r.err().map(|(i, e)| (block * BLOCK + i, e)))
+        .min_by_key(|&(i, _)| i)
+        .map_or(Ok(()), Err)
+}
+
+/// Like `convert::from_f32_slice`.
+pub fn from_f32_slice(mm: &[f32]) -> Result<Vec<Microns>, (usize, ConversionError)> {
+    let mut out = vec![Microns::ZERO; mm.len()];
+    from_f32_into(mm, as_um_slice_mut(&mut out))?;
+    Ok(out)
+}
+
+/// Like `convert::to_f32_slice`.
+pub fn to_f32_slice(microns: &[Microns]) -> Vec<f32> {
+    let mut out = vec![0.0; microns.len()];
+    to_f32_into(as_um_slice(microns), &mut out);
+    out
+}
+
+/// Like `convert::from_f32_into`.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn from_f32_into(mm: &[f32], um: &mut [i32]) -> Result<(), (usize, ConversionError)> {
+    assert_eq!(mm.len(), um.len(), "slices must have the same length");
+    first_error(
+        mm.par_chunks(BLOCK)
+            .zip(um.par_chunks_mut(BLOCK))
+            .map(|(mm, um)| convert::from_f32_into(mm, um))
+            .enumerate(),
+    )
+}
+
+/// Like `convert::to_f32_into`.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn to_f32_into(um: &[i32], mm: &mut [f32]) {
+    assert_eq!(um.len(), mm.len(), "slices must have the same length");
+    um.par_chunks(BLOCK)
+        .zip(mm.par_chunks_mut(BLOCK))
+        .for_each(|(um, mm)| convert::to_f32_into(um, mm));
+}
+
+/// Like `convert::offset`.
+pub fn offset(values: &mut [Microns], by: Microns) -> Result<(), (usize, ConversionError)> {
+    first_error(
+        values
+            .par_chunks_mut(BLOCK)
+            .map(|block| convert::offset(block, by))
+            .enumerate(),
+    )
+}
+
+/// Like `convert::add_assign`.
+///
+/// # Panics
+///
+/// If the slices differ in length.
+pub fn add_assign(values: &mut [Microns], rhs: &[Microns]) -> Result<(), (usize, ConversionError)> {
+    assert_eq!(values.len(), rhs.len(), "slices must have the same length");
+    first_error(
+        values
+            .par_chunks_mut(BLOCK)
+            .zip(rhs.par_chunks(BLOCK))
+            .map(|(values, rhs)| convert::add_assign(values, rhs))
+            .enumerate(),
+    )
+}
+
+/// Like `convert::min_max`.
+pub fn min_max(values: &[Microns]) -> Option<(Microns, Microns)> {
+    values
+        .par_chunks(BLOCK)
+        .filter_map(convert::min_max)
+        .reduce_with(|(lo, hi), (l, h)| (lo.min(l), hi.max(h)))
+}
+
+/// Like `Microns::checked_sum`: `None` only if the total doesn't fit.
+pub fn checked_sum(values: &[Microns]) -> Option<Microns> {
+    let total: i128 = values
+        .par_chunks(BLOCK)
+        .map(|block| block.iter().map(|m| m.0 as i64).sum::<i64>() as i128)
+        .sum();
+    i32::try_from(total).ok().map(Microns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_sequential() {
+        let n = 3 * BLOCK + 17;
+        let mut mm: Vec<f32> = (0..n).map(|i| (i as f32 * 0.37).sin() * 2000.0).collect();
+        let xs = from_f32_slice(&mm).unwrap();
+        assert_eq!(xs, convert::from_f32_slice(&mm).unwrap());
+        assert_eq!(to_f32_slice(&xs), convert::to_f32_slice(&xs));
+        assert_eq!(min_max(&xs), convert::min_max(&xs));
+        assert_eq!(checked_sum(&xs), Microns::checked_sum(xs.iter().copied()));
+
+        mm[2 * BLOCK + 3] = f32::NAN;
+        mm[BLOCK + 1] = 1e9;
+        assert_eq!(
+            from_f32_slice(&mm),
+            Err((BLOCK + 1, ConversionError::Overflow))
+        );
+
+        let mut ys = xs.clone();
+        offset(&mut ys, Microns(-3)).unwrap();
+        let mut zs = xs.clone();
+        convert::offset(&mut zs, Microns(-3)).unwrap();
+        assert_eq!(ys, zs);
+        add_assign(&mut ys, &xs).unwrap();
+        ys[n - 1] = Microns::MAX;
+        ys[2 * BLOCK] = Microns::MAX;
+        assert_eq!(
+            offset(&mut ys, Microns(1)),
+            Err((2 * BLOCK, ConversionError::Overflow))
+        );
+        assert_eq!(checked_sum(&[Microns::MAX, Microns::MAX]), None);
+        assert_eq!(min_max(&[]), None);
+    }
+}
@@ -0,0 +1,303 @@
+//! Decimal scanning shared by the `mm!` macro and string parsing.
+//!
+//! Numbers are kept as exact decimal digits rather than going through a
+//! float, so `1.001` is exactly 1001 µm.
+use crate::FixedDecimal;
+use core::fmt;
+use core::str::FromStr;
+
+/// `digits / 10^scale`, with a sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Decimal {
+    pub(crate) negative: bool,
+    pub(crate) digits: u128,
+    pub(crate) scale: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScanError {
+    NoDigits,
+    TooManyDigits,
+}
+
+/// Scans an optionally signed decimal like `-12.345` from the start of
+/// `bytes`, returning it with the number of bytes consumed. `_` separators
+/// between digits are skipped when `underscores` is set, as in Rust literals.
+pub(crate) const fn scan_decimal(
+    bytes: &[u8],
+    underscores: bool,
+) -> Result<(Decimal, usize), ScanError> {
+    let mut i = 0;
+    let mut negative = false;
+    if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+        negative = bytes[i] == b'-';
+        i += 1;
+    }
+    let (mut digits, mut scale) = (0u128, 0u32);
+    let (mut seen_digit, mut seen_point) = (false, false);
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b.is_ascii_digit() {
+            digits = match digits.checked_mul(10) {
+                Some(d) => match d.checked_add((b - b'0') as u128) {
+                    Some(d) => d,
+                    None => return Err(ScanError::TooManyDigits),
+                },
+                None => return Err(ScanError::TooManyDigits),
+            };
+            if seen_point {
+                scale += 1;
+            }
+            seen_digit = true;
+        } else if b == b'.' && !seen_point {
+            seen_point = true;
+        } else if !(b == b'_' && underscores && seen_digit) {
+            break;
+        }
+        i += 1;
+    }
+    if !seen_digit {
+        return Err(ScanError::NoDigits);
+    }
+    // keeps 10^scale times any unit denominator inside a u128
+    if scale > 30 {
+        return Err(ScanError::TooManyDigits);
+    }
+    Ok((
+        Decimal {
+            negative,
+            digits,
+            scale,
+        },
+        i,
+    ))
+}
+
+impl Decimal {
+    /// Converts to micrometers given `um_num / um_den` micrometers per unit,
+    /// as the quotient truncated toward zero and whether it was exact.
+    /// `None` if the result can't be an `i128`.
+    pub(crate) const fn to_um(self, um_num: u128, um_den: u128) -> Option<(i128, bool)> {
+        let num = match self.digits.checked_mul(um_num) {
+            Some(num) => num,
+            None => return None,
+        };
+        let den = 10u128.pow(self.scale) * um_den;
+        let (quot, exact) = (num / den, num % den == 0);
+        if quot > i128::MAX as u128 {
+            return None;
+        }
+        if self.negative {
+            Some((-(quot as i128), exact))
+        } else {
+            Some((quot as i128, exact))
+        }
+    }
+}
+
+/// Units `FromStr` accepts, as micrometers per unit `(num, den)`.
+const UNITS: [(&str, u128, u128); 9] = [
+    ("mm", 1000, 1),
+    ("um", 1, 1),
+    ("\u{b5}m", 1, 1),
+    ("\u{3bc}m", 1, 1),
+    ("nm", 1, 1000),
+    ("cm", 10_000, 1),
+    ("m", 1_000_000, 1),
+    ("in", 25_400, 1),
+    ("mil", 254, 10),
+];
+
+/// Why a string couldn't be parsed as Microns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParseMicronsError {
+    Empty,
+    /// No digits where the number should be.
+    InvalidNumber,
+    /// More digits, or decimal places, than can be handled exactly.
+    TooManyDigits,
+    /// Something other than a known unit after the number.
+    UnknownUnit,
+    OutOfRange,
+}
+
+impl fmt::Display for ParseMicronsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMicronsError::Empty => write!(f, "cannot parse a length from an empty string"),
+            ParseMicronsError::InvalidNumber => write!(f, "invalid number"),
+            ParseMicronsError::TooManyDigits => write!(f, "number has too many digits"),
+            ParseMicronsError::UnknownUnit => {
+                write!(
+                    f,
+                    "unknown unit, expected one of mm, um, \u{b5}m, nm, cm, m, in, mil"
+                )
+            }
+            ParseMicronsError::OutOfRange => write!(f, "length is out of range"),
+        }
+    }
+}
+
+impl core::error::Error for ParseMicronsError {}
+
+/// Parses a decimal with an optional unit suffix into whole micrometers,
+/// truncating toward zero. A bare number is millimeters.
+fn parse_um(s: &str) -> Result<i128, ParseMicronsError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseMicronsError::Empty);
+    }
+    let (dec, len) = scan_decimal(s.as_bytes(), false).map_err(scan_error)?;
+    let suffix = s[len..].trim_start();
+    let (num, den) = if suffix.is_empty() {
+        (1000, 1)
+    } else {
+        UNITS
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(suffix))
+            .map(|&(_, num, den)| (num, den))
+            .ok_or(ParseMicronsError::UnknownUnit)?
+    };
+    dec.to_um(num, den)
+        .map(|(um, _)| um)
+        .ok_or(ParseMicronsError::OutOfRange)
+}
+
+fn scan_error(e: ScanError) -> ParseMicronsError {
+    match e {
+        ScanError::NoDigits => ParseMicronsError::InvalidNumber,
+        ScanError::TooManyDigits => ParseMicronsError::TooManyDigits,
+    }
+}
+
+macro_rules! impl_from_str {
+    ($($t:ty),*) => {$(
+        impl FixedDecimal<3, $t> {
+            /// Reads millimeters like `-12.345` from the start of `bytes`,
+            /// truncating toward zero, and returns them with the number of
+            /// bytes used. Stops at the first byte that isn't part of the
+            /// number, so `b"12.5Y3"` gives 12.5 mm and 4. No whitespace or
+            /// unit suffix is accepted.
+            pub fn parse_ascii(bytes: &[u8]) -> Result<(Self, usize), ParseMicronsError> {
+                if bytes.is_empty() {
+                    return Err(ParseMicronsError::Empty);
+                }
+                let (dec, len) = scan_decimal(bytes, false).map_err(scan_error)?;
+                dec.to_um(1000, 1)
+                    .and_then(|(um, _)| <$t>::try_from(um).ok())
+                    .map(|um| (FixedDecimal(um), len))
+                    .ok_or(ParseMicronsError::OutOfRange)
+            }
+        }
+
+        /// Parses millimeters, or any unit in `"1.5"`, `"1.5mm"`, `"250um"`,
+        /// `"250\u{b5}m"`, `"0.010in"`, `"10mil"`, `"2.5cm"`, `"1m"` or
+        /// `"1500nm"`, truncating toward zero to whole micrometers.
+        impl FromStr for FixedDecimal<3, $t> {
+            type Err = ParseMicronsError;
+            fn from_str(s: &str) -> Result<Self, ParseMicronsError> {
+                let um = parse_um(s)?;
+                <$t>::try_from(um)
+                    .map(FixedDecimal)
+                    .map_err(|_| ParseMicronsError::OutOfRange)
+            }
+        }
+    )*};
+}
+impl_from_str!(i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_decimal() {
+        let dec = |negative, digits, scale| Decimal {
+            negative,
+            digits,
+            scale,
+        };
+        assert_eq!(
+            scan_decimal(b"12.345", false),
+            Ok((dec(false, 12345, 3), 6))
+        );
+        assert_eq!(scan_decimal(b"-0.5 Y1", false), Ok((dec(true, 5, 1), 4)));
+        assert_eq!(scan_decimal(b"+.25mm", false), Ok((dec(false, 25, 2), 4)));
+        assert_eq!(scan_decimal(b"7.", false), Ok((dec(false, 7, 0), 2)));
+        assert_eq!(
+            scan_decimal(b"1_000.5", true),
+            Ok((dec(false, 10005, 1), 7))
+        );
+        assert_eq!(scan_decimal(b"1_000.5", false), Ok((dec(false, 1, 0), 1)));
+        assert_eq!(scan_decimal(b"1.2.3", false), Ok((dec(false, 12, 1), 3)));
+        assert_eq!(scan_decimal(b"-", false), Err(ScanError::NoDigits));
+        assert_eq!(scan_decimal(b"_1", true), Err(ScanError::NoDigits));
+        assert_eq!(
+            scan_decimal(&[b'9'; 40], false),
+            Err(ScanError::TooManyDigits)
+        );
+    }
+
+    #[test]
+    fn test_to_um() {
+        let um = |s: &str, num, den| scan_decimal(s.as_bytes(), false).unwrap().0.to_um(num, den);
+        assert_eq!(um("1.25", 1000, 1), Some((1250, true)));
+        assert_eq!(um("-1.2505", 1000, 1), Some((-1250, false)));
+        assert_eq!(um("0.010", 25_400, 1), Some((254, true)));
+        assert_eq!(um("10", 254, 10), Some((254, true)));
+        assert_eq!(um("-2147483.648", 1000, 1), Some((i32::MIN as i128, true)));
+        assert_eq!(um(&"9".repeat(38), 1000, 1), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        use crate::{Microns, Microns64};
+        let parse = |s: &str| s.parse::<Microns>();
+        assert_eq!(parse("1.5"), Ok(Microns(1500)));
+        assert_eq!(parse("1.5mm"), Ok(Microns(1500)));
+        assert_eq!(parse(" -1.5 MM "), Ok(Microns(-1500)));
+        assert_eq!(parse("250um"), Ok(Microns(250)));
+        assert_eq!(parse("250\u{b5}m"), Ok(Microns(250)));
+        assert_eq!(parse("250\u{3bc}m"), Ok(Microns(250)));
+        assert_eq!(parse("0.010in"), Ok(Microns(254)));
+        assert_eq!(parse("10mil"), Ok(Microns(254)));
+        assert_eq!(parse("1mil"), Ok(Microns(25)));
+        assert_eq!(parse("2.5cm"), Ok(Microns(25_000)));
+        assert_eq!(parse("1m"), Ok(Microns::M));
+        assert_eq!(parse("-1999nm"), Ok(Microns(-1)));
+        assert_eq!(parse("0.0005"), Ok(Microns(0)));
+        assert_eq!(parse(""), Err(ParseMicronsError::Empty));
+        assert_eq!(parse("mm"), Err(ParseMicronsError::InvalidNumber));
+        assert_eq!(parse("1.5 ft"), Err(ParseMicronsError::UnknownUnit));
+        assert_eq!(parse("1e3"), Err(ParseMicronsError::UnknownUnit));
+        assert_eq!(parse("3km"), Err(ParseMicronsError::UnknownUnit));
+        assert_eq!(parse("2148m"), Err(ParseMicronsError::OutOfRange));
+        assert_eq!(
+            "2148m".parse::<Microns64>(),
+            Ok(Microns64::new(2_148_000_000))
+        );
+        assert_eq!(parse("1."), Ok(Microns::MM));
+        assert!(ParseMicronsError::UnknownUnit.to_string().contains("mil"));
+    }
+
+    #[test]
+    fn test_parse_ascii() {
+        use crate::{Microns, Microns64};
+        assert_eq!(Microns::parse_ascii(b"12.5Y3"), Ok((Microns(12_500), 4)));
+        assert_eq!(Microns::parse_ascii(b"-0.0019 "), Ok((Microns(-1), 7)));
+        assert_eq!(Microns::parse_ascii(b"+7."), Ok((Microns(7_000), 3)));
+        assert_eq!(Microns::parse_ascii(b""), Err(ParseMicronsError::Empty));
+        assert_eq!(
+            Microns::parse_ascii(b" 1"),
+            Err(ParseMicronsError::InvalidNumber)
+        );
+        assert_eq!(
+            Microns::parse_ascii(b"2147484"),
+            Err(ParseMicronsError::OutOfRange)
+        );
+        assert_eq!(
+            Microns64::parse_ascii(b"2147484*"),
+            Ok((Microns64::new(2_147_484_000), 7))
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! `proptest` strategies, so downstream crates don't need their own
+//! wrappers around `i32` strategies.
+//!
+//! ```
+//! use microns::{proptest::microns_between, Microns};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn abs_is_positive(m in microns_between(-Microns::M, Microns::M)) {
+//!         prop_assert!(m.abs() >= Microns::ZERO);
+//!     }
+//! }
+//! abs_is_positive();
+//! ```
+use crate::{FixedDecimal, Microns, MicronsInterval, Point2, Point3, Toleranced, Vec2, Vec3};
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::prelude::*;
+use ::proptest::strategy::Map;
+
+macro_rules! impl_arbitrary {
+    ($($t:ty),*) => {$(
+        /// Any value, `MIN` and `MAX` included.
+        impl<const SCALE: u32> Arbitrary for FixedDecimal<SCALE, $t> {
+            type Parameters = ();
+            type Strategy = Map<<$t as Arbitrary>::Strategy, fn($t) -> Self>;
+
+            fn arbitrary_with(_: ()) -> Self::Strategy {
+                any::<$t>().prop_map(FixedDecimal)
+            }
+        }
+    )*};
+}
+impl_arbitrary!(i32, i64);
+
+/// Any `Microns`; the same as `any::<Microns>()`.
+pub fn any_microns() -> impl Strategy<Value = Microns> {
+    any::<Microns>()
+}
+
+/// Values in `lo..=hi`.
+pub fn microns_between(lo: Microns, hi: Microns) -> impl Strategy<Value = Microns> {
+    (lo.0..=hi.0).prop_map(Microns)
+}
+
+/// Values in `-mm..=mm` millimeters, for inputs that stay clear of
+/// overflow, e.g. `microns_within_mm(1000)` for a one-meter machine.
+pub fn microns_within_mm(mm: i32) -> impl Strategy<Value = Microns> {
+    let limit = Microns::MM * mm.abs();
+    microns_between(-limit, limit)
+}
+
+/// Intervals with both ends in `lo..=hi`.
+pub fn interval_between(lo: Microns, hi: Microns) -> impl Strategy<Value = MicronsInterval> {
+    (microns_between(lo, hi), microns_between(lo, hi)).prop_map(|(a, b)| MicronsInterval::new(a, b))
+}
+
+/// Dimensions with a nominal in `lo..=hi` and each tolerance up to `max_tol`.
+pub fn toleranced_between(
+    lo: Microns,
+    hi: Microns,
+    max_tol: Microns,
+) -> impl Strategy<Value = Toleranced> {
+    let tol = || microns_between(Microns::ZERO, max_tol.abs());
+    (microns_between(lo, hi), tol(), tol())
+        .prop_map(|(nominal, plus, minus)| Toleranced::new(nominal, plus, minus))
+}
+
+/// Points in the box from corner `lo` to corner `hi`: each coordinate is
+/// in `lo..=hi` along its axis.
+pub fn point2_between(lo: Point2, hi: Point2) -> impl Strategy<Value = Point2> {
+    (microns_between(lo.x, hi.x), microns_between(lo.y, hi.y)).prop_map(|(x, y)| Point2::new(x, y))
+}
+
+/// Like `point2_between`, in three dimensions.
+pub fn point3_between(lo: Point3, hi: Point3) -> impl Strategy<Value = Point3> {
+    let axes = (
+        microns_between(lo.x, hi.x),
+        microns_between(lo.y, hi.y),
+        microns_between(lo.z, hi.z),
+    );
+    axes.prop_map(|(x, y, z)| Point3::new(x, y, z))
+}
+
+/// Vectors with each component in `lo..=hi` along its axis.
+pub fn vec2_between(lo: Vec2, hi: Vec2) -> impl Strategy<Value = Vec2> {
+    (microns_between(lo.x, hi.x), microns_between(lo.y, hi.y)).prop_map(|(x, y)| Vec2::new(x, y))
+}
+
+/// Like `vec2_between`, in three dimensions.
+pub fn vec3_between(lo: Vec3, hi: Vec3) -> impl Strategy<Value = Vec3> {
+    let axes = (
+        microns_between(lo.x, hi.x),
+        microns_between(lo.y, hi.y),
+        microns_between(lo.z, hi.z),
+    );
+    axes.prop_map(|(x, y, z)| Vec3::new(x, y, z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_strategies(
+            m in microns_within_mm(10),
+            i in interval_between(Microns(-5), Microns(5)),
+            t in toleranced_between(Microns::ZERO, Microns::MM, Microns(20)),
+        ) {
+            prop_assert!(m.within(-Microns::MM * 10..=Microns::MM * 10));
+            prop_assert!(i.lo() >= Microns(-5) && i.hi() <= Microns(5));
+            prop_assert!(t.lower() <= t.nominal && t.nominal <= t.upper());
+            prop_assert!(t.upper() - t.lower() <= Microns(40));
+        }
+
+        #[test]
+        fn test_geom_strategies(
+            p in point2_between(Point2::new(Microns(-5), Microns(10)), Point2::new(Microns(5), Microns(20))),
+            q in point3_between(Point3::new(Microns(0), Microns(-1), Microns::MM), Point3::new(Microns(1), Microns(0), Microns::CM)),
+            v in vec2_between(Vec2::new(Microns(-3), Microns(-3)), Vec2::new(Microns(3), Microns(-2))),
+            w in vec3_between(Vec3::default(), Vec3::new(Microns(1), Microns(2), Microns(3))),
+        ) {
+            prop_assert!(p.x.within(Microns(-5)..=Microns(5)) && p.y.within(Microns(10)..=Microns(20)));
+            prop_assert!(q.x.within(Microns(0)..=Microns(1)) && q.y.within(Microns(-1)..=Microns(0)));
+            prop_assert!(q.z.within(Microns::MM..=Microns::CM));
+            prop_assert!(v.x.within(Microns(-3)..=Microns(3)) && v.y.within(Microns(-3)..=Microns(-2)));
+            prop_assert!(w.x.within(Microns(0)..=Microns(1)) && w.z.within(Microns(0)..=Microns(3)));
+        }
+
+        #[test]
+        fn test_any_round_trips_display(m in any_microns()) {
+            prop_assert_eq!(m.to_string().parse::<Microns>(), Ok(m));
+        }
+    }
+}
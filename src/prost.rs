@@ -0,0 +1,98 @@
+//! Protobuf messages for Microns values and coordinates, built with `prost`.
+//!
+//! Lengths go on the wire as micrometer counts in `sint32` fields, which
+//! zigzag-encode so small negative offsets stay short. They match:
+//!
+//! ```proto
+//! message Microns { sint32 um = 1; }
+//! message Point3 { sint32 x_um = 1; sint32 y_um = 2; sint32 z_um = 3; }
+//! ```
+//!
+//! For a length inside your own message, declare an `int32` or `sint32`
+//! field and convert with `Microns(field)` and `m.0`.
+//!
+//! ```
+//! use microns::{prost::Point3Message, Microns, Point3};
+//! use prost::Message;
+//!
+//! let p = Point3::new(Microns(1500), Microns(-20), Microns(0));
+//! let bytes = Point3Message::from(p).encode_to_vec();
+//! assert_eq!(bytes, [0x08, 0xb8, 0x17, 0x10, 0x27]);
+//! let back = Point3Message::decode(bytes.as_slice()).unwrap();
+//! assert_eq!(Point3::from(back), p);
+//! ```
+use crate::{Microns, Point3};
+
+/// A single length, as `message Microns { sint32 um = 1; }`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MicronsMessage {
+    #[prost(sint32, tag = "1")]
+    pub um: i32,
+}
+
+/// An (x, y, z) coordinate, as
+/// `message Point3 { sint32 x_um = 1; sint32 y_um = 2; sint32 z_um = 3; }`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Point3Message {
+    #[prost(sint32, tag = "1")]
+    pub x_um: i32,
+    #[prost(sint32, tag = "2")]
+    pub y_um: i32,
+    #[prost(sint32, tag = "3")]
+    pub z_um: i32,
+}
+
+impl From<Microns> for MicronsMessage {
+    fn from(m: Microns) -> Self {
+        MicronsMessage { um: m.0 }
+    }
+}
+
+impl From<MicronsMessage> for Microns {
+    fn from(m: MicronsMessage) -> Self {
+        Microns(m.um)
+    }
+}
+
+impl From<Point3> for Point3Message {
+    fn from(p: Point3) -> Self {
+        Point3Message {
+            x_um: p.x.0,
+            y_um: p.y.0,
+            z_um: p.z.0,
+        }
+    }
+}
+
+impl From<Point3Message> for Point3 {
+    fn from(p: Point3Message) -> Self {
+        Point3::new(Microns(p.x_um), Microns(p.y_um), Microns(p.z_um))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::prost::Message;
+
+    #[test]
+    fn test_wire_format() {
+        let bytes = MicronsMessage::from(Microns(-1)).encode_to_vec();
+        assert_eq!(bytes, [0x08, 0x01]);
+        let m = MicronsMessage::decode(bytes.as_slice()).unwrap();
+        assert_eq!(Microns::from(m), Microns(-1));
+        // defaults are omitted, and missing fields read back as zero
+        assert!(MicronsMessage::from(Microns(0)).encode_to_vec().is_empty());
+        let p = Point3Message::decode(&[0x10, 0x04][..]).unwrap();
+        assert_eq!(
+            Point3::from(p),
+            Point3::new(Microns(0), Microns(2), Microns(0))
+        );
+        let extreme = Point3::new(Microns::MIN, Microns::MAX, Microns(0));
+        let bytes = Point3Message::from(extreme).encode_to_vec();
+        assert_eq!(
+            Point3::from(Point3Message::decode(bytes.as_slice()).unwrap()),
+            extreme
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Python bindings through PyO3, as a `Microns` class.
+//!
+//! Add it to your extension module with `register`:
+//!
+//! ```ignore
+//! #[pymodule]
+//! fn motion(m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     microns::python::register(m)
+//! }
+//! ```
+//!
+//! Conversions truncate toward zero exactly as in Rust. Out of range
+//! results raise `OverflowError`, and NaN or unparseable strings raise
+//! `ValueError`.
+use crate::{exact, ConversionError, Microns, RoundingMode};
+use pyo3::exceptions::{PyOverflowError, PyValueError, PyZeroDivisionError};
+use pyo3::prelude::*;
+use std::format;
+use std::string::{String, ToString};
+
+/// `Microns` as seen from Python.
+#[pyclass(
+    name = "Microns",
+    module = "microns",
+    frozen,
+    eq,
+    ord,
+    hash,
+    from_py_object
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PyMicrons(pub Microns);
+
+fn conversion_error(e: ConversionError) -> PyErr {
+    match e {
+        ConversionError::NaN => PyValueError::new_err(e.to_string()),
+        ConversionError::Overflow | ConversionError::Underflow => {
+            PyOverflowError::new_err(e.to_string())
+        }
+    }
+}
+
+#[pymethods]
+impl PyMicrons {
+    /// A whole number of micrometers.
+    #[new]
+    fn new(um: i32) -> Self {
+        PyMicrons(Microns(um))
+    }
+
+    #[staticmethod]
+    fn from_mm(mm: f64) -> PyResult<Self> {
+        Microns::try_from(mm)
+            .map(PyMicrons)
+            .map_err(conversion_error)
+    }
+
+    #[staticmethod]
+    fn from_inch(inch: f64) -> PyResult<Self> {
+        Microns::try_from_steps_f64(inch * 25_400.0)
+            .map(PyMicrons)
+            .map_err(conversion_error)
+    }
+
+    /// Any string `FromStr` accepts, such as `"1.5"`, `"1.5mm"` or `"250um"`.
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<Self> {
+        s.parse()
+            .map(PyMicrons)
+            .map_err(|e: crate::ParseMicronsError| PyValueError::new_err(e.to_string()))
+    }
+
+    #[getter]
+    fn um(&self) -> i32 {
+        self.0 .0
+    }
+
+    #[getter]
+    fn mm(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    #[getter]
+    fn inch(&self) -> f64 {
+        self.0.as_inch()
+    }
+
+    fn __add__(&self, other: &Self) -> PyResult<Self> {
+        self.0
+            .checked_add(other.0)
+            .map(PyMicrons)
+            .ok_or_else(|| PyOverflowError::new_err("attempt to add with overflow"))
+    }
+
+    fn __sub__(&self, other: &Self) -> PyResult<Self> {
+        self.0
+            .checked_sub(other.0)
+            .map(PyMicrons)
+            .ok_or_else(|| PyOverflowError::new_err("attempt to subtract with overflow"))
+    }
+
+    /// Like `Microns * f32`: exact, then truncated toward zero.
+    fn __mul__(&self, factor: f32) -> PyResult<Self> {
+        exact::mul(self.0 .0 as i128, factor, RoundingMode::Trunc)
+            .and_then(exact::narrow)
+            .map(PyMicrons)
+            .map_err(conversion_error)
+    }
+
+    fn __rmul__(&self, factor: f32) -> PyResult<Self> {
+        self.__mul__(factor)
+    }
+
+    /// Like `Microns / f32`: exact, then truncated toward zero.
+    fn __truediv__(&self, divisor: f32) -> PyResult<Self> {
+        if divisor == 0.0 {
+            return Err(PyZeroDivisionError::new_err("division by zero"));
+        }
+        exact::div(self.0 .0 as i128, divisor, RoundingMode::Trunc)
+            .and_then(exact::narrow)
+            .map(PyMicrons)
+            .map_err(conversion_error)
+    }
+
+    fn __neg__(&self) -> PyResult<Self> {
+        self.0
+            .checked_neg()
+            .map(PyMicrons)
+            .ok_or_else(|| PyOverflowError::new_err("attempt to negate with overflow"))
+    }
+
+    fn __abs__(&self) -> PyResult<Self> {
+        if self.0 < Microns::ZERO {
+            self.__neg__()
+        } else {
+            Ok(*self)
+        }
+    }
+
+    fn __bool__(&self) -> bool {
+        self.0 != Microns::ZERO
+    }
+
+    /// Millimeters with three decimals, e.g. `-12.345`.
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+impl From<Microns> for PyMicrons {
+    fn from(m: Microns) -> Self {
+        PyMicrons(m)
+    }
+}
+
+impl From<PyMicrons> for Microns {
+    fn from(m: PyMicrons) -> Self {
+        m.0
+    }
+}
+
+/// Adds the `Microns` class to `module`.
+pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyMicrons>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    #[test]
+    fn test_python_class() {
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "microns").unwrap();
+            register(&module).unwrap();
+            let locals = PyDict::new(py);
+            locals
+                .set_item("Microns", module.getattr("Microns").unwrap())
+                .unwrap();
+            let run =
+                |code: &str| py.run(&std::ffi::CString::new(code).unwrap(), None, Some(&locals));
+            run(r#"
+a = Microns.from_mm(1.2345)
+b = Microns.parse("0.010in")
+assert a.um == 1234 and b.um == 254
+assert (a + b).um == 1488 and (b - a).um == -980
+assert (a * 0.5).um == 617 and (2 * a).um == 2468 and (a / 3).um == 411
+assert abs(Microns(-5)) == Microns(5) and -a == Microns(-1234)
+assert b < a and max(a, b) is a and len({a, Microns(1234)}) == 1
+assert Microns.from_inch(0.5).um == 12700 and Microns(12700).inch == 0.5
+assert str(Microns(-1500)) == "-1.500" and repr(a) == "Microns(1234 = 1.234mm)"
+assert not Microns(0) and Microns(1).mm == 0.001
+"#)
+            .unwrap();
+            let raises = |code: &str, exc: &str| {
+                let err = run(code).unwrap_err();
+                assert_eq!(err.get_type(py).name().unwrap(), exc, "{code}");
+            };
+            raises("Microns.from_mm(1e10)", "OverflowError");
+            raises("Microns.from_mm(float('nan'))", "ValueError");
+            raises("Microns.parse('1ft')", "ValueError");
+            raises("Microns(2**31 - 1) + Microns(1)", "OverflowError");
+            raises("-Microns(-2**31)", "OverflowError");
+            raises("Microns(1) / 0", "ZeroDivisionError");
+        });
+    }
+}
@@ -0,0 +1,78 @@
+//! `rand` sampling, for fuzzing and Monte-Carlo tolerance analysis.
+//!
+//! `StandardUniform` covers every representable value, while
+//! `Uniform::new(lo, hi)` and `rng.random_range(lo..hi)` sample whole
+//! steps between two lengths.
+use crate::FixedDecimal;
+use rand::distr::uniform::{Error, SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
+
+/// The `Uniform` sampler for `FixedDecimal`, over the raw steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformFixedDecimal<const SCALE: u32, T>(UniformInt<T>);
+
+macro_rules! impl_random {
+    ($($t:ty),*) => {$(
+        impl<const SCALE: u32> Distribution<FixedDecimal<SCALE, $t>> for StandardUniform {
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> FixedDecimal<SCALE, $t> {
+                FixedDecimal(self.sample(rng))
+            }
+        }
+
+        impl<const SCALE: u32> SampleUniform for FixedDecimal<SCALE, $t> {
+            type Sampler = UniformFixedDecimal<SCALE, $t>;
+        }
+
+        impl<const SCALE: u32> UniformSampler for UniformFixedDecimal<SCALE, $t> {
+            type X = FixedDecimal<SCALE, $t>;
+
+            fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<$t>::new(low.borrow().0, high.borrow().0).map(UniformFixedDecimal)
+            }
+
+            fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+            where
+                B1: SampleBorrow<Self::X> + Sized,
+                B2: SampleBorrow<Self::X> + Sized,
+            {
+                UniformInt::<$t>::new_inclusive(low.borrow().0, high.borrow().0)
+                    .map(UniformFixedDecimal)
+            }
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+                FixedDecimal(self.0.sample(rng))
+            }
+        }
+    )*};
+}
+impl_random!(i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Microns, Microns64};
+    use rand::distr::{Distribution, StandardUniform, Uniform};
+    use rand::rngs::SmallRng;
+    use rand::{RngExt, SeedableRng};
+
+    #[test]
+    fn test_sampling() {
+        let mut rng = SmallRng::seed_from_u64(7);
+        let nominal = Uniform::new(Microns(-50), Microns(50)).unwrap();
+        for _ in 0..1000 {
+            let m = nominal.sample(&mut rng);
+            assert!(m >= Microns(-50) && m < Microns(50));
+            let m = rng.random_range(Microns::ZERO..=Microns(2));
+            assert!(m.within(Microns::ZERO..=Microns(2)));
+        }
+        let one = Uniform::new_inclusive(Microns64::MM, Microns64::MM).unwrap();
+        assert_eq!(one.sample(&mut rng), Microns64::MM);
+        assert!(Uniform::new(Microns(1), Microns(1)).is_err());
+        let any: Vec<Microns> = StandardUniform.sample_iter(&mut rng).take(8).collect();
+        assert!(any.iter().any(|m| m.0.unsigned_abs() > 1 << 24));
+    }
+}
@@ -0,0 +1,161 @@
+//! Evenly spaced Microns, for layer heights and probe grids.
+//!
+//! ```
+//! use microns::Microns;
+//!
+//! let layers: Vec<_> = Microns::range(Microns(200), Microns(1000), Microns(200)).collect();
+//! assert_eq!(layers, [Microns(200), Microns(400), Microns(600), Microns(800)]);
+//! let probes = Microns::range_inclusive(Microns::ZERO, Microns(-100), Microns(-50));
+//! assert_eq!(probes.count(), 3);
+//! ```
+use crate::Microns;
+use core::iter::FusedIterator;
+
+/// `start, start + step, ...` up to an end, built by `Microns::range` or
+/// `Microns::range_inclusive`.
+///
+/// A range can hold up to 2^32 + 1 values, so it's an `ExactSizeIterator`
+/// only where `usize` is 64 bits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MicronsRange {
+    start: i64,
+    step: i64,
+    /// Index of the next value from the front.
+    front: u64,
+    /// One past the index of the next value from the back.
+    back: u64,
+}
+
+impl MicronsRange {
+    fn new(start: Microns, end: Microns, step: Microns, inclusive: bool) -> Self {
+        assert!(step != Microns::ZERO, "step must not be zero");
+        let (span, step_abs) = (end.0 as i64 - start.0 as i64, (step.0 as i64).abs());
+        // the span in the direction of travel; nothing if `end` is behind `start`
+        let ahead = if step.0 > 0 { span } else { -span };
+        let count = match (ahead, inclusive) {
+            (..0, _) | (0, false) => 0,
+            (ahead, false) => (ahead + step_abs - 1) / step_abs,
+            (ahead, true) => ahead / step_abs + 1,
+        };
+        MicronsRange {
+            start: start.0 as i64,
+            step: step.0 as i64,
+            front: 0,
+            back: count as u64,
+        }
+    }
+
+    fn at(&self, index: u64) -> Microns {
+        Microns((self.start + self.step * index as i64) as i32)
+    }
+}
+
+impl Microns {
+    /// `start, start + step, ...` while before `end`. A negative `step`
+    /// counts down. Panics if `step` is zero.
+    pub fn range(start: Microns, end: Microns, step: Microns) -> MicronsRange {
+        MicronsRange::new(start, end, step, false)
+    }
+
+    /// Like `range`, but also yields `end` when a step lands on it exactly.
+    pub fn range_inclusive(start: Microns, end: Microns, step: Microns) -> MicronsRange {
+        MicronsRange::new(start, end, step, true)
+    }
+}
+
+impl Iterator for MicronsRange {
+    type Item = Microns;
+
+    fn next(&mut self) -> Option<Microns> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.at(self.front - 1))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Microns> {
+        self.front = self.front.saturating_add(n as u64).min(self.back);
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.back - self.front) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl DoubleEndedIterator for MicronsRange {
+    fn next_back(&mut self) -> Option<Microns> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.at(self.back))
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for MicronsRange {}
+
+impl FusedIterator for MicronsRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let r = |a, b, s| {
+            Microns::range(Microns(a), Microns(b), Microns(s))
+                .map(|m| m.0)
+                .collect::<Vec<_>>()
+        };
+        let ri = |a, b, s| {
+            Microns::range_inclusive(Microns(a), Microns(b), Microns(s))
+                .map(|m| m.0)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(r(0, 10, 3), [0, 3, 6, 9]);
+        assert_eq!(r(0, 9, 3), [0, 3, 6]);
+        assert_eq!(ri(0, 9, 3), [0, 3, 6, 9]);
+        assert_eq!(ri(0, 10, 3), [0, 3, 6, 9]);
+        assert_eq!(r(5, -5, -5), [5, 0]);
+        assert_eq!(ri(5, -5, -5), [5, 0, -5]);
+        assert_eq!(r(0, 0, 1), [0; 0]);
+        assert_eq!(ri(0, 0, 1), [0]);
+        assert_eq!(r(0, 10, -1), [0; 0]);
+        let all = Microns::range_inclusive(Microns::MIN, Microns::MAX, Microns::MAX);
+        assert_eq!(
+            all.rev().collect::<Vec<_>>(),
+            [Microns(2_147_483_646), Microns(-1), Microns::MIN]
+        );
+        let mut it = Microns::range(Microns::ZERO, Microns::MM, Microns(100));
+        assert_eq!(it.size_hint(), (10, Some(10)));
+        assert_eq!(it.nth(3), Some(Microns(300)));
+        assert_eq!(it.next_back(), Some(Microns(900)));
+        assert_eq!(it.size_hint(), (5, Some(5)));
+        assert_eq!(it.nth(100), None);
+        assert_eq!(it.next_back(), None);
+    }
+
+    #[test]
+    fn test_range_len() {
+        let every = Microns::range_inclusive(Microns::MIN, Microns::MAX, Microns(1));
+        let left = 1_u64 << 32;
+        match usize::try_from(left) {
+            Ok(len) => assert_eq!(every.size_hint(), (len, Some(len))),
+            Err(_) => assert_eq!(every.size_hint(), (usize::MAX, None)),
+        }
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(every.len(), left as usize);
+    }
+
+    #[test]
+    #[should_panic(expected = "step must not be zero")]
+    fn test_range_zero_step() {
+        let _ = Microns::range(Microns::ZERO, Microns::MM, Microns::ZERO);
+    }
+}
@@ -0,0 +1,203 @@
+//! Dimensionless scale factors applied in integer math.
+//!
+//! Scaling by a factor goes through `mul_ratio`, so it is exact and
+//! truncated toward zero once, instead of passing through an f32.
+use crate::{float, FixedDecimal, Microns};
+use core::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Percent holds hundredths of a percent: `Percent(10_000)` is 100%.
+pub struct Percent(pub i32);
+
+impl Percent {
+    pub const ZERO: Percent = Percent(0);
+    /// 100%, which leaves values unchanged.
+    pub const FULL: Percent = Percent(10_000);
+    /// Hundredths of a percent per percent.
+    const PER_PERCENT: i32 = 100;
+
+    /// From hundredths of a percent, also known as basis points.
+    pub const fn new(basis_points: i32) -> Self {
+        Percent(basis_points)
+    }
+
+    /// From a whole number of percent, e.g. `Percent::from_whole(95)`.
+    pub const fn from_whole(percent: i32) -> Self {
+        Percent(percent * Percent::PER_PERCENT)
+    }
+
+    /// From a float percentage, truncated toward zero to hundredths.
+    /// Panics if it doesn't fit.
+    pub fn from_f32(percent: f32) -> Self {
+        let bp = float::trunc(percent * Percent::PER_PERCENT as f32);
+        assert!(
+            bp >= i32::MIN as f32 && bp < i32::MAX as f32,
+            "Value out of range"
+        );
+        Percent(bp as i32)
+    }
+
+    /// The percentage as a float, e.g. `95.5`.
+    pub fn as_f32(self) -> f32 {
+        self.0 as f32 / Percent::PER_PERCENT as f32
+    }
+
+    /// The factor as a plain ratio, e.g. `0.955`.
+    pub fn as_ratio(self) -> f64 {
+        self.0 as f64 / Percent::FULL.0 as f64
+    }
+}
+
+impl Add for Percent {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Percent(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Percent {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Percent(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Percent {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Percent(-self.0)
+    }
+}
+
+/// Compounds two factors, truncated toward zero to hundredths of a percent:
+/// 50% of 50% is 25%. Panics if the result is out of range.
+impl Mul for Percent {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let bp = self.0 as i64 * rhs.0 as i64 / Percent::FULL.0 as i64;
+        Percent(i32::try_from(bp).expect("Value out of range"))
+    }
+}
+
+macro_rules! impl_mul_percent {
+    ($($t:ty),*) => {$(
+        /// Exact, truncated toward zero. Panics if the result is out of range.
+        impl<const SCALE: u32> Mul<Percent> for FixedDecimal<SCALE, $t> {
+            type Output = Self;
+            fn mul(self, rhs: Percent) -> Self {
+                self.mul_ratio(rhs.0 as i64, Percent::FULL.0 as i64)
+            }
+        }
+
+        impl<const SCALE: u32> Mul<FixedDecimal<SCALE, $t>> for Percent {
+            type Output = FixedDecimal<SCALE, $t>;
+            fn mul(self, rhs: FixedDecimal<SCALE, $t>) -> FixedDecimal<SCALE, $t> {
+                rhs * self
+            }
+        }
+    )*};
+}
+impl_mul_percent!(i32, i64);
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Ppm is a correction in parts per million: `Ppm(12)` stretches a length
+/// by 12 µm per meter, `Ppm(-12)` shrinks it.
+pub struct Ppm(pub i32);
+
+impl Ppm {
+    pub const ZERO: Ppm = Ppm(0);
+    const PER_UNIT: i64 = 1_000_000;
+
+    pub const fn new(ppm: i32) -> Self {
+        Ppm(ppm)
+    }
+
+    /// `m` with the correction applied, `m * (1 + ppm / 10^6)`.
+    /// Panics if the result is out of range.
+    pub fn apply(self, m: Microns) -> Microns {
+        m.mul_ratio(Ppm::PER_UNIT + self.0 as i64, Ppm::PER_UNIT)
+    }
+
+    /// Undoes `apply`, up to the truncation of each. Panics for `Ppm(-1_000_000)`,
+    /// which collapses every length to zero.
+    pub fn remove(self, m: Microns) -> Microns {
+        m.mul_ratio(Ppm::PER_UNIT, Ppm::PER_UNIT + self.0 as i64)
+    }
+
+    /// Just the change `apply` makes, `m * ppm / 10^6`.
+    pub fn delta(self, m: Microns) -> Microns {
+        m.mul_ratio(self.0 as i64, Ppm::PER_UNIT)
+    }
+}
+
+impl Add for Ppm {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Ppm(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Ppm {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Ppm(self.0 - rhs.0)
+    }
+}
+
+impl Neg for Ppm {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Ppm(-self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Microns64;
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(Microns(200) * Percent::FULL, Microns(200));
+        assert_eq!(Microns(200) * Percent::from_whole(95), Microns(190));
+        assert_eq!(Microns(100) * Percent(3_333), Microns(33));
+        assert_eq!(Microns(-100) * Percent(3_333), Microns(-33));
+        assert_eq!(
+            Percent(12_500) * Microns::MAX.midpoint(Microns::ZERO),
+            Microns(1_342_177_278)
+        );
+        assert_eq!(Microns64::M * Percent(1), Microns64::new(100));
+        assert_eq!(Percent::from_f32(95.55), Percent(9_555));
+        assert_eq!(Percent(9_550).as_f32(), 95.5);
+        assert_eq!(Percent(9_550).as_ratio(), 0.955);
+        assert_eq!(Percent::FULL - Percent::from_whole(5), Percent(9_500));
+        assert_eq!(Percent::from_whole(50) * Percent(3_333), Percent(1_666));
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_percent_overflow() {
+        let _ = Microns::MAX * Percent(10_001);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let growth = Ppm(12);
+        assert_eq!(growth.apply(Microns::M), Microns(1_000_012));
+        assert_eq!(growth.delta(Microns::M), Microns(12));
+        assert_eq!(growth.remove(Microns(1_000_012)), Microns::M);
+        assert_eq!((-growth).apply(Microns::M), Microns(999_988));
+        // 2 km of travel keeps every micron of the correction
+        assert_eq!(growth.apply(Microns(2_000_000_000)), Microns(2_000_024_000));
+        assert_eq!(growth.apply(Microns(-83_333)), Microns(-83_333));
+        assert_eq!(growth.delta(Microns(-83_334)), Microns(-1));
+        assert_eq!(Ppm::ZERO.apply(Microns::MAX), Microns::MAX);
+        assert_eq!(growth + Ppm(3) - Ppm(5), Ppm(10));
+    }
+}
@@ -0,0 +1,50 @@
+//! `schemars::JsonSchema`, matching the derived serde impls: a length is an
+//! integer number of micrometers. Fields serialized through one of the
+//! `microns::serde_as` modules take their schema from that module's `schema`
+//! function instead, e.g.
+//! `#[schemars(schema_with = "microns::serde_as::as_mm_f64::schema")]`.
+use crate::FixedDecimal;
+use alloc::borrow::Cow;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+
+macro_rules! impl_json_schema {
+    ($($t:ty => $name:literal, $format:literal;)*) => {$(
+        impl JsonSchema for FixedDecimal<3, $t> {
+            fn schema_name() -> Cow<'static, str> {
+                $name.into()
+            }
+
+            fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                json_schema!({
+                    "type": "integer",
+                    "format": $format,
+                    "minimum": <$t>::MIN,
+                    "maximum": <$t>::MAX,
+                    "description": "A length in integer micrometers",
+                })
+            }
+
+            fn inline_schema() -> bool {
+                true
+            }
+        }
+    )*};
+}
+impl_json_schema! {
+    i32 => "Microns", "int32";
+    i64 => "Microns64", "int64";
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Microns, Microns64};
+
+    #[test]
+    fn test_json_schema() {
+        let schema = schemars::schema_for!(Microns);
+        assert_eq!(schema.get("type").unwrap(), "integer");
+        assert_eq!(schema.get("maximum").unwrap(), i32::MAX);
+        let schema = schemars::schema_for!(Microns64);
+        assert_eq!(schema.get("format").unwrap(), "int64");
+    }
+}
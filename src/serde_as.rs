@@ -0,0 +1,352 @@
+//! Wire formats for `#[serde(with = "...")]`.
+//!
+//! The derived impls read and write the raw micrometer count. These modules
+//! pick another representation per field:
+//!
+//! ```
+//! # #[derive(serde::Serialize, serde::Deserialize)]
+//! # struct Profile {
+//! #[serde(with = "microns::serde_as::as_mm_f64")]
+//! layer_height: microns::Microns,
+//! # }
+//! ```
+//!
+//! - `as_um_i32`: `1250`, the same as the derived impls
+//! - `as_mm_f64`: `1.25`, truncated toward zero on the way in like
+//!   `Microns::try_from(f64)`, so it reads back exactly what it writes
+//! - `as_string`: `"1.250"`; reads anything `FromStr` does, such as `"1.25mm"`
+//! - `lenient`: writes `1250` and reads any of the above
+//!
+//! With the `serde_with` feature, `AsUm`, `AsMm`, `AsString` and
+//! `Lenient` do the same inside `Vec`, `Option`, maps and so on.
+use crate::Microns;
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use core::fmt;
+
+/// Integer micrometers.
+pub mod as_um_i32 {
+    use super::*;
+
+    /// The JSON schema for this representation, for
+    /// `#[schemars(schema_with = "microns::serde_as::as_um_i32::schema")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        <Microns as schemars::JsonSchema>::json_schema(generator)
+    }
+
+    pub fn serialize<S: Serializer>(m: &Microns, serializer: S) -> Result<S::Ok, S::Error> {
+        m.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Microns, D::Error> {
+        i32::deserialize(deserializer).map(Microns)
+    }
+}
+
+/// Floating-point millimeters.
+pub mod as_mm_f64 {
+    use super::*;
+
+    /// The JSON schema for this representation, for
+    /// `#[schemars(schema_with = "microns::serde_as::as_mm_f64::schema")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "number",
+            "description": "A length in millimeters",
+        })
+    }
+
+    pub fn serialize<S: Serializer>(m: &Microns, serializer: S) -> Result<S::Ok, S::Error> {
+        f64::from(*m).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Microns, D::Error> {
+        let mm = f64::deserialize(deserializer)?;
+        Microns::try_from(mm).map_err(de::Error::custom)
+    }
+}
+
+/// Decimal millimeters as a string.
+pub mod as_string {
+    use super::*;
+
+    /// The JSON schema for this representation, for
+    /// `#[schemars(schema_with = "microns::serde_as::as_string::schema")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "description": "Millimeters, or a length with a unit such as \"250um\"",
+        })
+    }
+
+    pub fn serialize<S: Serializer>(m: &Microns, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(m)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Microns, D::Error> {
+        let s = <alloc::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Integer micrometers out; in, whatever a third-party file happens to hold.
+/// Integers are micrometers, floats millimeters and strings are parsed
+/// with `FromStr`, so `1250`, `1.25`, `"1.25"` and `"1.25mm"` all read as
+/// 1.25 mm. Needs a self-describing format such as JSON: `2` and `2.0` differ.
+pub mod lenient {
+    use super::*;
+
+    /// The JSON schema for this representation, for
+    /// `#[schemars(schema_with = "microns::serde_as::lenient::schema")]`.
+    #[cfg(feature = "schemars")]
+    pub fn schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": ["integer", "number", "string"],
+            "description": "Integer micrometers, float millimeters, or a string like \"1.25mm\"",
+        })
+    }
+
+    pub fn serialize<S: Serializer>(m: &Microns, serializer: S) -> Result<S::Ok, S::Error> {
+        m.0.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Microns, D::Error> {
+        deserializer.deserialize_any(LenientVisitor)
+    }
+
+    struct LenientVisitor;
+
+    impl de::Visitor<'_> for LenientVisitor {
+        type Value = Microns;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "integer micrometers, float millimeters or a length string"
+            )
+        }
+
+        fn visit_i64<E: de::Error>(self, um: i64) -> Result<Microns, E> {
+            Microns::try_from(um).map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, um: u64) -> Result<Microns, E> {
+            i32::try_from(um)
+                .map(Microns)
+                .map_err(|_| E::custom(crate::ConversionError::Overflow))
+        }
+
+        /// Truncates like `visit_str`, so `1.001` and `"1.001"` agree.
+        fn visit_f64<E: de::Error>(self, mm: f64) -> Result<Microns, E> {
+            Microns::try_from(mm).map_err(E::custom)
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Microns, E> {
+            s.parse().map_err(E::custom)
+        }
+    }
+}
+
+// `serde_with` adapters, one per module above, for `#[serde_as]` fields
+// where the `Microns` sits inside a collection.
+#[cfg(feature = "serde_with")]
+mod adapters {
+    use super::*;
+    use serde_with::{DeserializeAs, SerializeAs};
+
+    macro_rules! impl_adapter {
+        ($($(#[$doc:meta])* $name:ident => $module:ident,)*) => {$(
+            $(#[$doc])*
+            pub struct $name;
+
+            impl SerializeAs<Microns> for $name {
+                fn serialize_as<S: Serializer>(m: &Microns, serializer: S) -> Result<S::Ok, S::Error> {
+                    $module::serialize(m, serializer)
+                }
+            }
+
+            impl<'de> DeserializeAs<'de, Microns> for $name {
+                fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Microns, D::Error> {
+                    $module::deserialize(deserializer)
+                }
+            }
+        )*};
+    }
+    impl_adapter! {
+        /// Like `as_um_i32`.
+        AsUm => as_um_i32,
+        /// Like `as_mm_f64`, for use in `#[serde_as]`:
+        ///
+        /// ```
+        /// use microns::{serde_as::AsMm, Microns};
+        /// use serde_with::serde_as;
+        ///
+        /// #[serde_as]
+        /// #[derive(serde::Serialize, serde::Deserialize)]
+        /// struct Profile {
+        ///     #[serde_as(as = "Vec<AsMm>")]
+        ///     layers: Vec<Microns>,
+        ///     #[serde_as(as = "Option<AsMm>")]
+        ///     brim: Option<Microns>,
+        /// }
+        /// ```
+        AsMm => as_mm_f64,
+        /// Like `as_string`.
+        AsString => as_string,
+        /// Like `lenient`.
+        Lenient => lenient,
+    }
+}
+#[cfg(feature = "serde_with")]
+pub use adapters::{AsMm, AsString, AsUm, Lenient};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Fields {
+        #[serde(with = "as_um_i32")]
+        um: Microns,
+        #[serde(with = "as_mm_f64")]
+        mm: Microns,
+        #[serde(with = "as_string")]
+        text: Microns,
+    }
+
+    #[test]
+    fn test_representations() {
+        let fields = Fields {
+            um: Microns(1250),
+            mm: Microns(1250),
+            text: Microns(-1250),
+        };
+        let json = serde_json::to_string(&fields).unwrap();
+        assert_eq!(json, r#"{"um":1250,"mm":1.25,"text":"-1.250"}"#);
+        assert_eq!(serde_json::from_str::<Fields>(&json).unwrap(), fields);
+        let loose: Fields = serde_json::from_str(r#"{"um":1,"mm":2,"text":"0.1in"}"#).unwrap();
+        assert_eq!((loose.mm, loose.text), (Microns(2000), Microns(2540)));
+        assert!(serde_json::from_str::<Fields>(r#"{"um":1,"mm":1e10,"text":"1"}"#).is_err());
+        assert!(serde_json::from_str::<Fields>(r#"{"um":1,"mm":1,"text":"1ft"}"#).is_err());
+    }
+
+    #[test]
+    fn test_mm_f64_round_trip() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Mm(#[serde(with = "as_mm_f64")] Microns);
+        let json = serde_json::to_string(&Mm(Microns(1001))).unwrap();
+        assert_eq!(json, "1.001");
+        assert_eq!(
+            serde_json::from_str::<Mm>(&json).unwrap(),
+            Mm(Microns(1001))
+        );
+        for um in (-2_000_000..=2_000_000).step_by(7) {
+            let json = serde_json::to_string(&Mm(Microns(um))).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Mm>(&json).unwrap(),
+                Mm(Microns(um)),
+                "{json}"
+            );
+        }
+    }
+
+    /// `use microns::*` mustn't shadow the `serde` crate.
+    mod glob_import {
+        use crate::*;
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Part {
+            #[serde(with = "serde_as::as_mm_f64")]
+            width: Microns,
+        }
+
+        #[test]
+        fn test_derive_after_glob_import() {
+            let part = Part { width: Microns(3) };
+            assert_eq!(serde_json::to_string(&part).unwrap(), r#"{"width":0.003}"#);
+        }
+    }
+
+    #[test]
+    fn test_lenient() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Lenient(#[serde(with = "lenient")] Microns);
+        let read = |json: &str| serde_json::from_str::<Lenient>(json).map(|l| l.0);
+        for json in ["1250", "1.25", r#""1.25""#, r#""1.25mm""#, r#""1250um""#] {
+            assert_eq!(read(json).unwrap(), Microns(1250), "{json}");
+        }
+        assert_eq!(read("-3").unwrap(), Microns(-3));
+        for mm in ["1.001", "-0.057", "1.0015", "-2.0009", "2147483.647"] {
+            let text = format!(r#""{mm}""#);
+            assert_eq!(read(mm).unwrap(), read(&text).unwrap(), "{mm}");
+        }
+        assert_eq!(read("1.001").unwrap(), Microns(1001));
+        assert!(read("3000000000").is_err());
+        assert!(read("-3000000000").is_err());
+        assert!(read("1e10").is_err());
+        assert!(read("true").is_err());
+        assert_eq!(serde_json::to_string(&Lenient(Microns(5))).unwrap(), "5");
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_schemas() {
+        let mut generator = schemars::SchemaGenerator::default();
+        assert_eq!(
+            as_mm_f64::schema(&mut generator).get("type").unwrap(),
+            "number"
+        );
+        assert_eq!(
+            as_string::schema(&mut generator).get("type").unwrap(),
+            "string"
+        );
+        assert_eq!(
+            as_um_i32::schema(&mut generator).get("type").unwrap(),
+            "integer"
+        );
+        assert_eq!(
+            lenient::schema(&mut generator)
+                .get("type")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[test]
+    fn test_serde_with_adapters() {
+        use std::collections::HashMap;
+
+        #[serde_with::serde_as]
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Profile {
+            #[serde_as(as = "Vec<AsMm>")]
+            layers: Vec<Microns>,
+            #[serde_as(as = "HashMap<_, AsString>")]
+            offsets: HashMap<String, Microns>,
+            #[serde_as(as = "Option<super::Lenient>")]
+            brim: Option<Microns>,
+        }
+        let profile = Profile {
+            layers: vec![Microns(200), Microns(150)],
+            offsets: HashMap::from([("z".to_string(), Microns(-50))]),
+            brim: None,
+        };
+        let json = serde_json::to_string(&profile).unwrap();
+        assert_eq!(
+            json,
+            r#"{"layers":[0.2,0.15],"offsets":{"z":"-0.050"},"brim":null}"#
+        );
+        assert_eq!(serde_json::from_str::<Profile>(&json).unwrap(), profile);
+        let brim = r#"{"layers":[],"offsets":{},"brim":"5mm"}"#;
+        assert_eq!(
+            serde_json::from_str::<Profile>(brim).unwrap().brim,
+            Some(Microns(5000))
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! SSE2 kernels for `convert`, four lanes at a time.
+//!
+//! SSE2 is part of the x86_64 baseline, so no runtime detection is needed.
+//! Each kernel matches the scalar loop bit for bit: a block holding a value
+//! that would fail is redone by the scalar loop, which then reports it.
+use crate::convert::scalar;
+use crate::{ConversionError, Microns};
+use core::arch::x86_64::*;
+
+pub(crate) fn from_f32_into(mm: &[f32], um: &mut [i32]) -> Result<(), (usize, ConversionError)> {
+    let blocks = mm.len() / 4;
+    // SAFETY: SSE2 is always available on x86_64, and every load and store
+    // is of four elements inside the slices
+    unsafe {
+        let lo = _mm_set1_ps(f32::from(Microns::MIN));
+        let hi = _mm_set1_ps(f32::from(Microns::MAX));
+        let per_mm = _mm_set1_ps(1000.0);
+        for b in 0..blocks {
+            let i = b * 4;
+            let v = _mm_loadu_ps(mm.as_ptr().add(i));
+            // both comparisons are false for NaN
+            let ok = _mm_and_ps(_mm_cmpgt_ps(v, lo), _mm_cmplt_ps(v, hi));
+            if _mm_movemask_ps(ok) != 0b1111 {
+                return scalar::from_f32_into(&mm[i..i + 4], &mut um[i..i + 4])
+                    .map_err(|(j, e)| (i + j, e));
+            }
+            let steps = _mm_cvttps_epi32(_mm_mul_ps(v, per_mm));
+            _mm_storeu_si128(um.as_mut_ptr().add(i).cast(), steps);
+        }
+    }
+    let i = blocks * 4;
+    scalar::from_f32_into(&mm[i..], &mut um[i..]).map_err(|(j, e)| (i + j, e))
+}
+
+pub(crate) fn to_f32_into(um: &[i32], mm: &mut [f32]) {
+    let blocks = um.len() / 4;
+    // SAFETY: as in `from_f32_into`
+    unsafe {
+        let per_mm = _mm_set1_ps(1000.0);
+        for b in 0..blocks {
+            let i = b * 4;
+            let v = _mm_loadu_si128(um.as_ptr().add(i).cast());
+            _mm_storeu_ps(
+                mm.as_mut_ptr().add(i),
+                _mm_div_ps(_mm_cvtepi32_ps(v), per_mm),
+            );
+        }
+    }
+    let i = blocks * 4;
+    scalar::to_f32_into(&um[i..], &mut mm[i..]);
+}
+
+/// Lanes of `a + b` that wrapped: the sum's sign differs from both inputs'.
+///
+/// # Safety
+///
+/// Only needs SSE2.
+unsafe fn overflowed(a: __m128i, b: __m128i, sum: __m128i) -> bool {
+    let wrapped = _mm_and_si128(_mm_xor_si128(a, sum), _mm_xor_si128(b, sum));
+    _mm_movemask_ps(_mm_castsi128_ps(wrapped)) != 0
+}
+
+pub(crate) fn offset(values: &mut [i32], by: i32) -> Result<(), (usize, ConversionError)> {
+    let blocks = values.len() / 4;
+    // SAFETY: as in `from_f32_into`
+    unsafe {
+        let b = _mm_set1_epi32(by);
+        for block in 0..blocks {
+            let i = block * 4;
+            let p = values.as_mut_ptr().add(i).cast::<__m128i>();
+            let a = _mm_loadu_si128(p);
+            let sum = _mm_add_epi32(a, b);
+            if overflowed(a, b, sum) {
+                return scalar::offset(&mut values[i..i + 4], by).map_err(|(j, e)| (i + j, e));
+            }
+            _mm_storeu_si128(p, sum);
+        }
+    }
+    let i = blocks * 4;
+    scalar::offset(&mut values[i..], by).map_err(|(j, e)| (i + j, e))
+}
+
+pub(crate) fn add_assign(values: &mut [i32], rhs: &[i32]) -> Result<(), (usize, ConversionError)> {
+    let blocks = values.len() / 4;
+    // SAFETY: as in `from_f32_into`
+    unsafe {
+        for block in 0..blocks {
+            let i = block * 4;
+            let p = values.as_mut_ptr().add(i).cast::<__m128i>();
+            let a = _mm_loadu_si128(p);
+            let b = _mm_loadu_si128(rhs.as_ptr().add(i).cast());
+            let sum = _mm_add_epi32(a, b);
+            if overflowed(a, b, sum) {
+                return scalar::add_assign(&mut values[i..i + 4], &rhs[i..i + 4])
+                    .map_err(|(j, e)| (i + j, e));
+            }
+            _mm_storeu_si128(p, sum);
+        }
+    }
+    let i = blocks * 4;
+    scalar::add_assign(&mut values[i..], &rhs[i..]).map_err(|(j, e)| (i + j, e))
+}
+
+pub(crate) fn min_max(values: &[i32]) -> Option<(i32, i32)> {
+    let blocks = values.len() / 4;
+    if blocks == 0 {
+        return scalar::min_max(values);
+    }
+    let (mut lo, mut hi) = ([0; 4], [0; 4]);
+    // SAFETY: as in `from_f32_into`
+    unsafe {
+        let first = _mm_loadu_si128(values.as_ptr().cast());
+        let (mut min, mut max) = (first, first);
+        for block in 1..blocks {
+            let v = _mm_loadu_si128(values.as_ptr().add(block * 4).cast());
+            // SSE2 has no 32-bit min or max, so select through a mask
+            let below = _mm_cmplt_epi32(v, min);
+            min = _mm_or_si128(_mm_and_si128(below, v), _mm_andnot_si128(below, min));
+            let above = _mm_cmpgt_epi32(v, max);
+            max = _mm_or_si128(_mm_and_si128(above, v), _mm_andnot_si128(above, max));
+        }
+        _mm_storeu_si128(lo.as_mut_ptr().cast(), min);
+        _mm_storeu_si128(hi.as_mut_ptr().cast(), max);
+    }
+    let tail = &values[blocks * 4..];
+    let lo = lo.iter().chain(tail).min().copied()?;
+    let hi = hi.iter().chain(tail).max().copied()?;
+    Some((lo, hi))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values around every boundary the kernels special-case.
+    fn samples() -> Vec<f32> {
+        let edge = f32::from(Microns::MAX);
+        let mut xs = vec![0.0, -0.0, 0.0005, -0.0015, 1.001, 0.3, -2147.0];
+        xs.extend([edge, -edge, edge.next_down(), (-edge).next_up(), 1e-40]);
+        xs.extend((0..40).map(|i| (i as f32 * 1234.567).sin() * 3000.0));
+        xs
+    }
+
+    #[test]
+    fn test_conversions_match_scalar() {
+        let mm = samples();
+        for len in 0..mm.len() {
+            let (mut fast, mut slow) = (vec![0; len], vec![0; len]);
+            let got = from_f32_into(&mm[..len], &mut fast);
+            let want = scalar::from_f32_into(&mm[..len], &mut slow);
+            assert_eq!(got, want, "{len}");
+            assert_eq!(fast, slow, "{len}");
+        }
+        let finite: Vec<f32> = mm.iter().copied().filter(|x| x.abs() < 2147.0).collect();
+        let mut um = vec![0; finite.len()];
+        from_f32_into(&finite, &mut um).unwrap();
+        let (mut fast, mut slow) = (vec![0.0; um.len()], vec![0.0; um.len()]);
+        to_f32_into(&um, &mut fast);
+        scalar::to_f32_into(&um, &mut slow);
+        assert!(fast
+            .iter()
+            .zip(&slow)
+            .all(|(a, b)| a.to_bits() == b.to_bits()));
+        let mut nan = [1.0; 9];
+        nan[6] = f32::NAN;
+        assert_eq!(
+            from_f32_into(&nan, &mut [0; 9]),
+            Err((6, ConversionError::NaN))
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_matches_scalar() {
+        let mut values: Vec<i32> = (0..23).map(|i| i * 90_000_000 - 1_000_000_000).collect();
+        values[13] = i32::MIN + 5;
+        let rhs: Vec<i32> = (0..23).map(|i| (i % 5 - 2) * 400_000_000).collect();
+        for by in [0, 7, -7, i32::MAX / 2, i32::MIN] {
+            let (mut fast, mut slow) = (values.clone(), values.clone());
+            assert_eq!(offset(&mut fast, by), scalar::offset(&mut slow, by), "{by}");
+            assert_eq!(fast, slow, "{by}");
+        }
+        let (mut fast, mut slow) = (values.clone(), values.clone());
+        assert_eq!(
+            add_assign(&mut fast, &rhs),
+            scalar::add_assign(&mut slow, &rhs)
+        );
+        assert_eq!(fast, slow);
+        for len in 0..values.len() {
+            assert_eq!(
+                min_max(&values[..len]),
+                scalar::min_max(&values[..len]),
+                "{len}"
+            );
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! `sqlx` column support: a length is stored as its raw count, so Microns is
+//! an `INTEGER` column and Microns64 a `BIGINT`, in any database whose
+//! driver supports those.
+use crate::FixedDecimal;
+use ::sqlx::decode::Decode;
+use ::sqlx::encode::{Encode, IsNull};
+use ::sqlx::error::BoxDynError;
+use ::sqlx::{Database, Type};
+
+macro_rules! impl_sqlx {
+    ($($t:ty),*) => {$(
+        impl<const SCALE: u32, DB: Database> Type<DB> for FixedDecimal<SCALE, $t>
+        where
+            $t: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <$t as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <$t as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, const SCALE: u32, DB: Database> Encode<'q, DB> for FixedDecimal<SCALE, $t>
+        where
+            $t: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as Database>::ArgumentBuffer,
+            ) -> Result<IsNull, BoxDynError> {
+                self.0.encode_by_ref(buf)
+            }
+        }
+
+        impl<'r, const SCALE: u32, DB: Database> Decode<'r, DB> for FixedDecimal<SCALE, $t>
+        where
+            $t: Decode<'r, DB>,
+        {
+            fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                <$t as Decode<'r, DB>>::decode(value).map(FixedDecimal)
+            }
+        }
+    )*};
+}
+impl_sqlx!(i32, i64);
+
+#[cfg(test)]
+mod tests {
+    use crate::{Microns, Microns64};
+    use ::sqlx::{Connection, SqliteConnection};
+
+    #[tokio::test]
+    async fn test_sqlite_round_trip() {
+        let mut conn = SqliteConnection::connect(":memory:").await.unwrap();
+        let um: Microns = ::sqlx::query_scalar("SELECT ?")
+            .bind(Microns(-1250))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(um, Microns(-1250));
+        let long = Microns64::new(5_000_000_000);
+        let back: Microns64 = ::sqlx::query_scalar("SELECT ?")
+            .bind(long)
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(back, long);
+        let sum: Microns = ::sqlx::query_scalar("SELECT ? + ?")
+            .bind(Microns(1))
+            .bind(Microns(2))
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(sum, Microns(3));
+        // fits Microns64 but not Microns
+        let too_long = ::sqlx::query_scalar::<_, Microns>("SELECT ?")
+            .bind(long)
+            .fetch_one(&mut conn)
+            .await;
+        assert!(too_long.is_err());
+    }
+}
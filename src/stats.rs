@@ -0,0 +1,166 @@
+//! Descriptive statistics over slices of Microns, for repeatability and
+//! flatness reports.
+//!
+//! Sums are accumulated exactly in `i128`, so no precision is lost however
+//! long the slice is. Each function returns `None` for an empty slice.
+//!
+//! ```
+//! use microns::{stats, Microns, Percent};
+//!
+//! let probes = [Microns(1002), Microns(998), Microns(1001), Microns(999)];
+//! assert_eq!(stats::mean(&probes), Some(Microns(1000)));
+//! assert_eq!(stats::range(&probes), Some(microns::Microns64::new(4)));
+//! # #[cfg(feature = "alloc")]
+//! assert_eq!(stats::percentile(&probes, Percent::from_whole(75)), Some(Microns(1001)));
+//! ```
+#[cfg(feature = "alloc")]
+use crate::Percent;
+use crate::{convert, Microns, Microns64, RoundingMode};
+
+pub fn min(values: &[Microns]) -> Option<Microns> {
+    values.iter().copied().min()
+}
+
+pub fn max(values: &[Microns]) -> Option<Microns> {
+    values.iter().copied().max()
+}
+
+/// `max - min`, as a Microns64 because the spread of the whole range
+/// doesn't fit in a Microns.
+pub fn range(values: &[Microns]) -> Option<Microns64> {
+    convert::min_max(values).map(|(lo, hi)| Microns64::new(hi.0 as i64 - lo.0 as i64))
+}
+
+/// The exact mean, rounded to the nearest micrometer with ties away from
+/// zero.
+pub fn mean(values: &[Microns]) -> Option<Microns> {
+    if values.is_empty() {
+        return None;
+    }
+    let sum: i128 = values.iter().map(|m| m.0 as i128).sum();
+    let mean = RoundingMode::HalfUp.div_i128(sum, values.len() as i128);
+    Some(Microns(mean as i32))
+}
+
+/// The middle value; for an even count, the mean of the two middle values
+/// rounded like `mean`.
+#[cfg(feature = "alloc")]
+pub fn median(values: &[Microns]) -> Option<Microns> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    let mid = sorted.len() / 2;
+    let (below, &mut upper, _) = sorted.select_nth_unstable(mid);
+    if values.len() % 2 == 1 {
+        return Some(upper);
+    }
+    let lower = below.iter().copied().max()?;
+    let sum = lower.0 as i128 + upper.0 as i128;
+    Some(Microns(RoundingMode::HalfUp.div_i128(sum, 2) as i32))
+}
+
+/// The nearest-rank percentile: the smallest value with at least `p` of the
+/// values at or below it. Always one of the values, so `0%` is the minimum
+/// and `100%` the maximum.
+///
+/// # Panics
+///
+/// If `p` is outside `0%..=100%`.
+#[cfg(feature = "alloc")]
+pub fn percentile(values: &[Microns], p: Percent) -> Option<Microns> {
+    assert!(
+        (Percent::ZERO..=Percent::FULL).contains(&p),
+        "percentile must be between 0% and 100%"
+    );
+    if values.is_empty() {
+        return None;
+    }
+    let n = values.len() as u128;
+    let rank = (n * p.0 as u128).div_ceil(Percent::FULL.0 as u128).max(1);
+    let mut sorted = values.to_vec();
+    Some(*sorted.select_nth_unstable(rank as usize - 1).1)
+}
+
+/// The population standard deviation in millimeters. The sum of squared
+/// deviations is exact; only the final division and square root round.
+#[cfg(feature = "std")]
+pub fn std_dev(values: &[Microns]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let n = values.len() as i128;
+    let sum: i128 = values.iter().map(|m| m.0 as i128).sum();
+    let squares: i128 = values.iter().map(|m| (m.0 as i128).pow(2)).sum();
+    // with sum = q n + r, the squared deviations total squares - q^2 n - 2 q r - r^2 / n
+    let (q, r) = (sum.div_euclid(n), sum.rem_euclid(n));
+    let whole = squares - q * q * n - 2 * q * r;
+    let variance = (whole as f64 - (r * r) as f64 / n as f64) / n as f64;
+    Some(variance.max(0.0).sqrt() / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let xs = [Microns(5), Microns(-3), Microns(9), Microns(-3), Microns(2)];
+        assert_eq!((min(&xs), max(&xs)), (Some(Microns(-3)), Some(Microns(9))));
+        assert_eq!(mean(&xs), Some(Microns(2)));
+        assert_eq!(mean(&[Microns(-1), Microns(-2)]), Some(Microns(-2)));
+        let extremes = [Microns::MAX, Microns::MAX, Microns::MIN];
+        assert_eq!(mean(&extremes[..2]), Some(Microns::MAX));
+        assert_eq!(range(&extremes), Some(Microns64::new(u32::MAX as i64)));
+        for f in [min, max, mean] {
+            assert_eq!(f(&[]), None);
+        }
+        assert_eq!(range(&[]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_median() {
+        let xs = [Microns(5), Microns(-3), Microns(9), Microns(-3), Microns(2)];
+        assert_eq!(median(&xs), Some(Microns(2)));
+        assert_eq!(median(&xs[..4]), Some(Microns(1)));
+        assert_eq!(median(&[Microns(-1), Microns(-2)]), Some(Microns(-2)));
+        assert_eq!(median(&[Microns::MAX, Microns::MAX]), Some(Microns::MAX));
+        assert_eq!(median(&[]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_percentile() {
+        let xs: Vec<_> = (1..=10).rev().map(Microns).collect();
+        let pct = |p| percentile(&xs, Percent::from_whole(p));
+        assert_eq!(pct(0), Some(Microns(1)));
+        assert_eq!(pct(10), Some(Microns(1)));
+        assert_eq!(pct(11), Some(Microns(2)));
+        assert_eq!(pct(50), Some(Microns(5)));
+        assert_eq!(pct(100), Some(Microns(10)));
+        assert_eq!(percentile(&xs, Percent::new(9_001)), Some(Microns(10)));
+        assert_eq!(percentile(&[], Percent::FULL), None);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "between 0% and 100%")]
+    fn test_percentile_out_of_range() {
+        percentile(&[Microns(1)], Percent::new(10_001));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_std_dev() {
+        let xs = [2, 4, 4, 4, 5, 5, 7, 9].map(Microns);
+        assert_eq!(std_dev(&xs), Some(0.002));
+        assert_eq!(std_dev(&[Microns(7)]), Some(0.0));
+        assert_eq!(std_dev(&[]), None);
+        // a tiny spread on a huge offset survives, unlike a naive f64 sum
+        let far: Vec<_> = xs.iter().map(|m| Microns(m.0 + 2_000_000_000)).collect();
+        assert_eq!(std_dev(&far), Some(0.002));
+        let wide = [Microns::MIN, Microns::MAX];
+        assert_eq!(std_dev(&wide), Some(2_147_483.647_5));
+    }
+}
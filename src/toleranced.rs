@@ -0,0 +1,243 @@
+//! Dimensions with a tolerance band, `nominal +plus/-minus`.
+//!
+//! Arithmetic combines tolerances worst case: the limits of a sum or
+//! difference are the extreme combinations of the operands' limits.
+use crate::{Microns, MicronsInterval};
+use core::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which way a feature's size moves as material is added.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatureKind {
+    /// A shaft or boss: more material makes it bigger.
+    External,
+    /// A hole or slot: more material makes it smaller.
+    Internal,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Toleranced is a nominal size with the allowed deviation above and below.
+/// `plus` and `minus` are both magnitudes: `Toleranced::new(Microns(10_000),
+/// Microns(20), Microns(5))` allows `9.995..=10.020` mm.
+pub struct Toleranced {
+    pub nominal: Microns,
+    pub plus: Microns,
+    pub minus: Microns,
+}
+
+impl Toleranced {
+    /// The signs of `plus` and `minus` are ignored.
+    pub const fn new(nominal: Microns, plus: Microns, minus: Microns) -> Self {
+        Toleranced {
+            nominal,
+            plus: plus.abs(),
+            minus: minus.abs(),
+        }
+    }
+
+    /// `nominal ± tol`.
+    pub const fn symmetric(nominal: Microns, tol: Microns) -> Self {
+        Toleranced::new(nominal, tol, tol)
+    }
+
+    /// The smallest allowed size, `nominal - minus`.
+    pub const fn lower(self) -> Microns {
+        self.nominal.sub(self.minus)
+    }
+
+    /// The largest allowed size, `nominal + plus`.
+    pub const fn upper(self) -> Microns {
+        self.nominal.add(self.plus)
+    }
+
+    /// Whether `measured` is within the limits, inclusive.
+    pub const fn contains(self, measured: Microns) -> bool {
+        self.lower().0 <= measured.0 && measured.0 <= self.upper().0
+    }
+
+    /// The middle of the band, rounded toward zero. Differs from `nominal`
+    /// when the tolerance is unilateral or unequal.
+    pub const fn mid(self) -> Microns {
+        self.lower().midpoint(self.upper())
+    }
+
+    /// The limit at which the part has the most material: the upper limit
+    /// of an external feature, the lower limit of an internal one.
+    pub const fn max_material(self, kind: FeatureKind) -> Microns {
+        match kind {
+            FeatureKind::External => self.upper(),
+            FeatureKind::Internal => self.lower(),
+        }
+    }
+
+    /// The limit at which the part has the least material.
+    pub const fn min_material(self, kind: FeatureKind) -> Microns {
+        match kind {
+            FeatureKind::External => self.lower(),
+            FeatureKind::Internal => self.upper(),
+        }
+    }
+
+    /// The total width of the band, `plus + minus`.
+    pub const fn band(self) -> Microns {
+        self.plus.add(self.minus)
+    }
+
+    pub const fn to_interval(self) -> MicronsInterval {
+        MicronsInterval::new(self.lower(), self.upper())
+    }
+}
+
+impl From<Microns> for Toleranced {
+    fn from(nominal: Microns) -> Self {
+        Toleranced::symmetric(nominal, Microns::ZERO)
+    }
+}
+
+impl From<Toleranced> for MicronsInterval {
+    fn from(t: Toleranced) -> Self {
+        t.to_interval()
+    }
+}
+
+impl Add for Toleranced {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Toleranced {
+            nominal: self.nominal + rhs.nominal,
+            plus: self.plus + rhs.plus,
+            minus: self.minus + rhs.minus,
+        }
+    }
+}
+
+impl Sub for Toleranced {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Toleranced {
+            nominal: self.nominal - rhs.nominal,
+            plus: self.plus + rhs.minus,
+            minus: self.minus + rhs.plus,
+        }
+    }
+}
+
+impl Neg for Toleranced {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Toleranced {
+            nominal: -self.nominal,
+            plus: self.minus,
+            minus: self.plus,
+        }
+    }
+}
+
+impl Mul<i32> for Toleranced {
+    type Output = Self;
+    fn mul(self, rhs: i32) -> Self {
+        let (plus, minus) = if rhs < 0 {
+            (self.minus, self.plus)
+        } else {
+            (self.plus, self.minus)
+        };
+        Toleranced {
+            nominal: self.nominal * rhs,
+            plus: plus * rhs.abs(),
+            minus: minus * rhs.abs(),
+        }
+    }
+}
+
+/// Tolerances are drawn as magnitudes small enough that `lower`, `upper`
+/// and `band` all fit in Microns.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Toleranced {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let nominal: Microns = u.arbitrary()?;
+        let headroom = |limit: i32, by: i32| limit.checked_sub(by).unwrap_or(i32::MAX);
+        let plus = u.int_in_range(0..=headroom(i32::MAX, nominal.0))?;
+        let minus = u.int_in_range(0..=headroom(nominal.0, i32::MIN).min(i32::MAX - plus))?;
+        Ok(Toleranced::new(nominal, Microns(plus), Microns(minus)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toleranced_limits() {
+        let bore = Toleranced::new(Microns(10_000), Microns(20), Microns(-5));
+        assert_eq!(bore.minus, Microns(5));
+        assert_eq!(
+            (bore.lower(), bore.upper()),
+            (Microns(9_995), Microns(10_020))
+        );
+        assert!(bore.contains(Microns(9_995)) && bore.contains(Microns(10_020)));
+        assert!(!bore.contains(Microns(10_021)));
+        assert_eq!(bore.mid(), Microns(10_007));
+        assert_eq!(bore.band(), Microns(25));
+        assert_eq!(bore.max_material(FeatureKind::Internal), Microns(9_995));
+        assert_eq!(bore.min_material(FeatureKind::Internal), Microns(10_020));
+        assert_eq!(bore.max_material(FeatureKind::External), Microns(10_020));
+        assert_eq!(
+            MicronsInterval::from(bore),
+            MicronsInterval::new(Microns(9_995), Microns(10_020))
+        );
+    }
+
+    #[test]
+    fn test_toleranced_stack() {
+        let a = Toleranced::new(Microns(20_000), Microns(10), Microns(0));
+        let b = Toleranced::symmetric(Microns(5_000), Microns(3));
+        assert_eq!(
+            a + b,
+            Toleranced::new(Microns(25_000), Microns(13), Microns(3))
+        );
+        let gap = a - b;
+        assert_eq!(
+            gap,
+            Toleranced::new(Microns(15_000), Microns(13), Microns(3))
+        );
+        assert_eq!(gap.to_interval(), (a.to_interval() - b.to_interval()));
+        assert_eq!(
+            -a,
+            Toleranced::new(Microns(-20_000), Microns(0), Microns(10))
+        );
+        assert_eq!(
+            a * 3,
+            Toleranced::new(Microns(60_000), Microns(30), Microns(0))
+        );
+        assert_eq!(a * -1, -a);
+        assert_eq!(Toleranced::from(Microns(7)).band(), Microns::ZERO);
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_limits_fit() {
+        use arbitrary::{Arbitrary, Unstructured};
+        let buffers: [&[u8]; 5] = [
+            &[0xff; 16],
+            &[0; 16],
+            &[0xff, 0xff, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xff],
+            &[
+                0, 0, 0, 0x80, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            ],
+            &[
+                0x13, 0x57, 0x9b, 0xdf, 0x24, 0x68, 0xac, 0xe0, 0x11, 0x22, 0x33, 0x44,
+            ],
+        ];
+        for bytes in buffers {
+            let t = Toleranced::arbitrary(&mut Unstructured::new(bytes)).unwrap();
+            assert!(t.plus >= Microns::ZERO && t.minus >= Microns::ZERO);
+            assert!(t.lower() <= t.nominal && t.nominal <= t.upper());
+            assert_eq!(t.band().0 as i64, t.upper().0 as i64 - t.lower().0 as i64);
+            assert!(t.contains(t.nominal) && t.contains(t.mid()));
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Microns that remember what truncation threw away.
+//!
+//! Each float step truncates like `Microns::from(f32)`, but the sub-micron
+//! remainder is kept and folded into the next step, so a long chain of
+//! small moves ends within a micron of the exact total instead of losing
+//! up to a micron per step.
+use crate::Microns;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+/// TrackedMicrons is a quantized value plus the residual, in micrometers,
+/// that truncation has dropped so far. The residual has the sign of the
+/// exact total and is always less than one micrometer in magnitude.
+pub struct TrackedMicrons {
+    value: Microns,
+    residual: f64,
+}
+
+impl TrackedMicrons {
+    pub const fn new(value: Microns) -> Self {
+        TrackedMicrons {
+            value,
+            residual: 0.0,
+        }
+    }
+
+    /// Converts millimeters exactly as `Microns::from(f32)` does, keeping
+    /// the truncated remainder.
+    pub fn from_f32(val: f32) -> Self {
+        let value =
+            Microns::try_from_f32(val).unwrap_or_else(|e| panic!("Value out of range: {e}"));
+        // the same f32 product `try_from_f32` truncates
+        let um = val * 1000.0;
+        TrackedMicrons {
+            value,
+            residual: um as f64 - value.0 as f64,
+        }
+    }
+
+    fn from_um(um: f64) -> Self {
+        let value =
+            Microns::try_trunc_steps_f64(um).unwrap_or_else(|e| panic!("Value out of range: {e}"));
+        TrackedMicrons {
+            value,
+            residual: um - value.0 as f64,
+        }
+    }
+
+    /// The exact total in micrometers, as far as f64 can tell.
+    fn total(self) -> f64 {
+        self.value.0 as f64 + self.residual
+    }
+
+    /// The quantized value, truncated toward zero.
+    pub const fn value(self) -> Microns {
+        self.value
+    }
+
+    /// The remainder truncation has dropped, in micrometers.
+    pub const fn residual(self) -> f64 {
+        self.residual
+    }
+
+    /// Returns the residual and forgets it, leaving just `value()`.
+    pub fn take_error(&mut self) -> f64 {
+        core::mem::take(&mut self.residual)
+    }
+}
+
+impl From<Microns> for TrackedMicrons {
+    fn from(value: Microns) -> Self {
+        TrackedMicrons::new(value)
+    }
+}
+
+impl From<TrackedMicrons> for Microns {
+    fn from(tracked: TrackedMicrons) -> Self {
+        tracked.value
+    }
+}
+
+impl Add<f32> for TrackedMicrons {
+    type Output = Self;
+    fn add(self, rhs: f32) -> Self {
+        TrackedMicrons::from_um(self.total() + rhs as f64 * 1000.0)
+    }
+}
+
+impl Sub<f32> for TrackedMicrons {
+    type Output = Self;
+    fn sub(self, rhs: f32) -> Self {
+        TrackedMicrons::from_um(self.total() - rhs as f64 * 1000.0)
+    }
+}
+
+impl Add<Microns> for TrackedMicrons {
+    type Output = Self;
+    fn add(self, rhs: Microns) -> Self {
+        TrackedMicrons::from_um(self.total() + rhs.0 as f64)
+    }
+}
+
+impl Sub<Microns> for TrackedMicrons {
+    type Output = Self;
+    fn sub(self, rhs: Microns) -> Self {
+        TrackedMicrons::from_um(self.total() - rhs.0 as f64)
+    }
+}
+
+impl Mul<f32> for TrackedMicrons {
+    type Output = Self;
+    fn mul(self, rhs: f32) -> Self {
+        TrackedMicrons::from_um(self.total() * rhs as f64)
+    }
+}
+
+impl Div<i32> for TrackedMicrons {
+    type Output = Self;
+    fn div(self, rhs: i32) -> Self {
+        assert!(rhs != 0, "attempt to divide by zero");
+        TrackedMicrons::from_um(self.total() / rhs as f64)
+    }
+}
+
+impl AddAssign<f32> for TrackedMicrons {
+    fn add_assign(&mut self, rhs: f32) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<f32> for TrackedMicrons {
+    fn sub_assign(&mut self, rhs: f32) {
+        *self = *self - rhs;
+    }
+}
+
+impl AddAssign<Microns> for TrackedMicrons {
+    fn add_assign(&mut self, rhs: Microns) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Microns> for TrackedMicrons {
+    fn sub_assign(&mut self, rhs: Microns) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<f32> for TrackedMicrons {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<i32> for TrackedMicrons {
+    fn div_assign(&mut self, rhs: i32) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracked_no_drift() {
+        let mut plain = Microns::ZERO;
+        let mut tracked = TrackedMicrons::default();
+        for _ in 0..1000 {
+            plain += 0.0005;
+            tracked += 0.0005;
+        }
+        assert_eq!(plain, Microns::ZERO);
+        assert_eq!(tracked.value(), Microns(500));
+        assert!(tracked.residual().abs() < 0.001);
+
+        let mut down = TrackedMicrons::new(Microns(10));
+        for _ in 0..4 {
+            down -= 0.0025;
+        }
+        assert_eq!(down.value(), Microns(0));
+    }
+
+    #[test]
+    fn test_from_f32_matches_microns() {
+        assert_eq!(TrackedMicrons::from_f32(2.3).value(), Microns(2300));
+        for i in -20_000..=20_000 {
+            let mm = i as f32 * 0.0137;
+            let tracked = TrackedMicrons::from_f32(mm);
+            assert_eq!(tracked.value(), Microns::from(mm), "{mm}");
+            assert!(tracked.residual().abs() < 1.0);
+            assert!(tracked.residual() * mm as f64 >= 0.0, "{mm}");
+        }
+    }
+
+    #[test]
+    fn test_tracked_ops() {
+        let third = TrackedMicrons::new(Microns(1000)) / 3;
+        assert_eq!(third.value(), Microns(333));
+        assert!((third.residual() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!((third * 3.0).value(), Microns(1000));
+        let mut neg = TrackedMicrons::from_f32(-0.0015);
+        assert_eq!(neg.value(), Microns(-1));
+        assert!(neg.residual() < 0.0);
+        assert!((neg.take_error() + 0.5).abs() < 1e-3);
+        assert_eq!(neg.residual(), 0.0);
+        assert_eq!(Microns::from(neg + Microns(3)), Microns(2));
+    }
+}
@@ -0,0 +1,117 @@
+//! Constructors and accessors for units other than millimeters.
+//!
+//! Float inputs are truncated toward zero like `Microns::try_from(f64)`,
+//! so a decimal like `1.001` mm gives 1001 µm even though the scaled float
+//! is a hair below it. The constructors panic with the same message as
+//! `Microns::from(f32)` when out of range.
+use crate::FixedDecimal;
+
+macro_rules! impl_units {
+    ($t:ty) => {
+        impl FixedDecimal<3, $t> {
+            pub const INCH: Self = FixedDecimal(25_400);
+            /// A thousandth of an inch is 25.4 µm, so this is truncated to 25 µm.
+            /// For exact multiples use `Microns::INCH.mul_ratio(mils, 1000)`.
+            pub const MIL: Self = FixedDecimal(25);
+
+            fn from_um_f64(um: f64) -> Self {
+                Self::try_from_steps_f64(um).unwrap_or_else(|e| panic!("Value out of range: {e}"))
+            }
+
+            pub fn from_mm(mm: f64) -> Self {
+                Self::from_um_f64(mm * 1000.0)
+            }
+
+            pub const fn from_um(um: $t) -> Self {
+                FixedDecimal(um)
+            }
+
+            /// Truncates toward zero to whole micrometers.
+            pub fn from_nm(nm: i64) -> Self {
+                <$t>::try_from(nm / 1000)
+                    .map(FixedDecimal)
+                    .expect("Value out of range")
+            }
+
+            pub fn from_cm(cm: f64) -> Self {
+                Self::from_um_f64(cm * 10_000.0)
+            }
+
+            pub fn from_m(m: f64) -> Self {
+                Self::from_um_f64(m * 1_000_000.0)
+            }
+
+            pub fn from_inch(inch: f64) -> Self {
+                Self::from_um_f64(inch * 25_400.0)
+            }
+
+            pub fn from_mil(mil: f64) -> Self {
+                // 25.4 isn't exact in binary, so scale by whole numbers instead
+                Self::from_um_f64(mil * 25_400.0 / 1000.0)
+            }
+
+            pub fn as_mm(self) -> f64 {
+                f64::from(self)
+            }
+
+            pub fn as_um(self) -> $t {
+                self.0
+            }
+
+            pub fn as_inch(self) -> f64 {
+                self.0 as f64 / 25_400.0
+            }
+        }
+    };
+}
+impl_units!(i32);
+impl_units!(i64);
+
+#[cfg(test)]
+mod tests {
+    use crate::Microns;
+
+    #[test]
+    fn test_constructors() {
+        assert_eq!(Microns::from_mm(1.25), Microns(1250));
+        assert_eq!(Microns::from_mm(0.3), Microns(300));
+        assert_eq!(Microns::from_mm(1.001), Microns(1001));
+        assert_eq!(Microns::from_mm(-0.057), Microns(-57));
+        assert_eq!(Microns::from_mm(1.0015), Microns(1001));
+        assert_eq!(Microns::from_cm(0.1001), Microns(1001));
+        assert_eq!(Microns::from_m(0.001001), Microns(1001));
+        assert_eq!(Microns::from_inch(0.3), Microns(7620));
+        assert_eq!(Microns::from_mil(0.3), Microns(7));
+        for um in -100_000..=100_000 {
+            assert_eq!(Microns::from_mm(Microns(um).as_mm()), Microns(um));
+        }
+        assert_eq!(Microns::from_um(-750), Microns(-750));
+        assert_eq!(Microns::from_nm(1_999), Microns(1));
+        assert_eq!(Microns::from_nm(-1_999), Microns(-1));
+        assert_eq!(Microns::from_cm(1.5), Microns(15_000));
+        assert_eq!(Microns::from_m(2.0), Microns(2_000_000));
+        assert_eq!(Microns::from_inch(0.01), Microns(254));
+        assert_eq!(Microns::from_inch(-1.0), -Microns::INCH);
+        assert_eq!(Microns::from_mil(10.0), Microns(254));
+        assert_eq!(Microns::from_mil(1.0), Microns(25));
+    }
+
+    #[test]
+    fn test_accessors() {
+        assert_eq!(Microns(1250).as_mm(), 1.25);
+        assert_eq!(Microns(1250).as_um(), 1250);
+        assert_eq!(Microns(-12_700).as_inch(), -0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_out_of_range() {
+        let _ = Microns::from_m(3000.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value out of range")]
+    fn test_from_nm_out_of_range() {
+        let _ = Microns::from_nm(i64::MAX);
+    }
+}
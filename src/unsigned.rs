@@ -0,0 +1,206 @@
+//! Unsigned lengths, for sizes that can't be negative.
+//!
+//! Coordinates are `Microns`; diameters, layer heights and distances are
+//! `UMicrons`. Going between the two is fallible in both directions.
+use crate::{float, ConversionError, Microns};
+use core::iter::Sum;
+use core::ops::{Add, AddAssign, Div, Mul, Rem, Sub, SubAssign};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// UMicrons holds a non-negative length in micrometers, up to about 4.29 km.
+pub struct UMicrons(pub u32);
+
+impl UMicrons {
+    pub const ZERO: UMicrons = UMicrons(0);
+    pub const MAX: UMicrons = UMicrons(u32::MAX);
+    pub const MM: UMicrons = UMicrons(1000);
+
+    pub const fn new(um: u32) -> Self {
+        UMicrons(um)
+    }
+
+    /// Converts millimeters, truncating toward zero like `Microns::from(f32)`.
+    /// Anything that truncates to below zero is an `Underflow`.
+    pub fn try_from_f32(val: f32) -> Result<Self, ConversionError> {
+        if val.is_nan() {
+            return Err(ConversionError::NaN);
+        }
+        let um = float::trunc(val * 1000.0);
+        if um < 0.0 {
+            return Err(ConversionError::Underflow);
+        }
+        if um >= u32::MAX as f32 {
+            return Err(ConversionError::Overflow);
+        }
+        Ok(UMicrons(um as u32))
+    }
+
+    pub const fn checked_add(self, rhs: UMicrons) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(v) => Some(UMicrons(v)),
+            None => None,
+        }
+    }
+
+    /// `None` if `rhs` is longer than `self`.
+    pub const fn checked_sub(self, rhs: UMicrons) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(v) => Some(UMicrons(v)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_sub(self, rhs: UMicrons) -> Self {
+        UMicrons(self.0.saturating_sub(rhs.0))
+    }
+
+    /// The distance between two lengths.
+    pub const fn abs_diff(self, other: UMicrons) -> Self {
+        UMicrons(self.0.abs_diff(other.0))
+    }
+}
+
+impl Microns {
+    /// `|self|` as a length; never overflows, even for `Microns::MIN`.
+    pub const fn unsigned_abs(self) -> UMicrons {
+        UMicrons(self.0.unsigned_abs())
+    }
+}
+
+/// Fails with `Underflow` for negative values.
+impl TryFrom<Microns> for UMicrons {
+    type Error = ConversionError;
+    fn try_from(m: Microns) -> Result<Self, ConversionError> {
+        u32::try_from(m.0)
+            .map(UMicrons)
+            .map_err(|_| ConversionError::Underflow)
+    }
+}
+
+/// Fails with `Overflow` above `Microns::MAX`.
+impl TryFrom<UMicrons> for Microns {
+    type Error = ConversionError;
+    fn try_from(u: UMicrons) -> Result<Self, ConversionError> {
+        i32::try_from(u.0)
+            .map(Microns)
+            .map_err(|_| ConversionError::Overflow)
+    }
+}
+
+impl From<UMicrons> for f32 {
+    fn from(other: UMicrons) -> Self {
+        other.0 as f32 / 1000.0
+    }
+}
+
+impl From<UMicrons> for f64 {
+    fn from(other: UMicrons) -> Self {
+        other.0 as f64 / 1000.0
+    }
+}
+
+impl Add for UMicrons {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        UMicrons(self.0 + rhs.0)
+    }
+}
+
+impl Sub for UMicrons {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        UMicrons(self.0 - rhs.0)
+    }
+}
+
+impl Mul<u32> for UMicrons {
+    type Output = Self;
+    fn mul(self, rhs: u32) -> Self {
+        UMicrons(self.0 * rhs)
+    }
+}
+
+impl Div<u32> for UMicrons {
+    type Output = Self;
+    fn div(self, rhs: u32) -> Self {
+        UMicrons(self.0 / rhs)
+    }
+}
+
+impl Rem for UMicrons {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        UMicrons(self.0 % rhs.0)
+    }
+}
+
+impl AddAssign for UMicrons {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for UMicrons {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for UMicrons {
+    fn sum<I: Iterator<Item = UMicrons>>(iter: I) -> Self {
+        iter.fold(UMicrons::ZERO, |acc, u| {
+            acc.checked_add(u).expect("attempt to sum with overflow")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_umicrons_conversions() {
+        assert_eq!(UMicrons::try_from(Microns(400)), Ok(UMicrons(400)));
+        assert_eq!(
+            UMicrons::try_from(Microns(-1)),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(Microns::try_from(UMicrons(400)), Ok(Microns(400)));
+        assert_eq!(
+            Microns::try_from(UMicrons::MAX),
+            Err(ConversionError::Overflow)
+        );
+        assert_eq!(Microns::MIN.unsigned_abs(), UMicrons(1 << 31));
+        assert_eq!(UMicrons::try_from_f32(0.4), Ok(UMicrons(400)));
+        assert_eq!(UMicrons::try_from_f32(-0.0005), Ok(UMicrons::ZERO));
+        assert_eq!(
+            UMicrons::try_from_f32(-0.001),
+            Err(ConversionError::Underflow)
+        );
+        assert_eq!(UMicrons::try_from_f32(5e6), Err(ConversionError::Overflow));
+        assert_eq!(f32::from(UMicrons(1500)), 1.5);
+    }
+
+    #[test]
+    fn test_umicrons_ops() {
+        let (a, b) = (UMicrons(300), UMicrons(200));
+        assert_eq!(a + b, UMicrons(500));
+        assert_eq!(a - b, UMicrons(100));
+        assert_eq!(b.checked_sub(a), None);
+        assert_eq!(b.saturating_sub(a), UMicrons::ZERO);
+        assert_eq!(b.abs_diff(a), UMicrons(100));
+        assert_eq!(a * 2 / 3, b);
+        assert_eq!(a % b, UMicrons(100));
+        assert_eq!([a, b].into_iter().sum::<UMicrons>(), UMicrons(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to sum with overflow")]
+    fn test_umicrons_sum_overflow() {
+        let _: UMicrons = [UMicrons::MAX, UMicrons(1)].into_iter().sum();
+    }
+}
@@ -0,0 +1,55 @@
+//! Conversions to and from `uom` lengths.
+//!
+//! uom stores lengths in meters, where most micrometer counts have no exact
+//! `f64`, so reading one back in µm can land a hair below it. Converting
+//! back therefore rounds to the nearest micrometer instead of truncating,
+//! so every Microns survives the round trip.
+use crate::{ConversionError, Microns};
+use ::uom::si::f64::Length;
+use ::uom::si::length::micrometer;
+
+impl From<Microns> for Length {
+    fn from(m: Microns) -> Self {
+        Length::new::<micrometer>(m.0 as f64)
+    }
+}
+
+/// Rounds to the nearest micrometer, ties away from zero.
+impl TryFrom<Length> for Microns {
+    type Error = ConversionError;
+    fn try_from(length: Length) -> Result<Self, ConversionError> {
+        Microns::try_from_steps_f64(length.get::<micrometer>().round())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::uom::si::length::{inch, millimeter};
+
+    #[test]
+    fn test_uom_conversions() {
+        for m in [
+            Microns(1250),
+            Microns(-1),
+            Microns::MIN,
+            Microns::MAX,
+            Microns(123_457),
+        ] {
+            assert_eq!(Microns::try_from(Length::from(m)), Ok(m));
+        }
+        assert_eq!(Length::from(Microns(1500)).get::<millimeter>(), 1.5);
+        assert_eq!(
+            Microns::try_from(Length::new::<inch>(1.0)),
+            Ok(Microns::INCH)
+        );
+        assert_eq!(
+            Microns::try_from(Length::new::<millimeter>(0.0004)),
+            Ok(Microns::ZERO)
+        );
+        assert_eq!(
+            Microns::try_from(Length::new::<millimeter>(-1e10)),
+            Err(ConversionError::Underflow)
+        );
+    }
+}
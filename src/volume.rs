@@ -0,0 +1,113 @@
+//! Volume in cubic microns, the result of multiplying an area by a length.
+use crate::{Microns, SquareMicrons};
+use core::iter::Sum;
+use core::ops::{Add, Mul, Neg, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// CubicMicrons holds a volume in µm³, wide enough for the product of
+/// any three Microns.
+pub struct CubicMicrons(pub i128);
+
+impl CubicMicrons {
+    pub const ZERO: CubicMicrons = CubicMicrons(0);
+
+    pub fn abs(&self) -> Self {
+        CubicMicrons(self.0.abs())
+    }
+
+    /// The volume in cubic millimeters.
+    pub fn as_mm3(self) -> f64 {
+        self.0 as f64 / 1e9
+    }
+
+    /// The volume in milliliters, i.e. cubic centimeters.
+    pub fn as_ml(self) -> f64 {
+        self.0 as f64 / 1e12
+    }
+}
+
+impl Microns {
+    pub fn squared(self) -> SquareMicrons {
+        self * self
+    }
+
+    pub fn cubed(self) -> CubicMicrons {
+        self.squared() * self
+    }
+}
+
+impl Mul<Microns> for SquareMicrons {
+    type Output = CubicMicrons;
+    fn mul(self, rhs: Microns) -> CubicMicrons {
+        CubicMicrons(self.0 as i128 * rhs.0 as i128)
+    }
+}
+
+impl Mul<SquareMicrons> for Microns {
+    type Output = CubicMicrons;
+    fn mul(self, rhs: SquareMicrons) -> CubicMicrons {
+        rhs * self
+    }
+}
+
+/// Converts to cubic millimeters.
+impl From<CubicMicrons> for f64 {
+    fn from(other: CubicMicrons) -> Self {
+        other.as_mm3()
+    }
+}
+
+impl Add for CubicMicrons {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        CubicMicrons(self.0 + rhs.0)
+    }
+}
+
+impl Sub for CubicMicrons {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        CubicMicrons(self.0 - rhs.0)
+    }
+}
+
+impl Neg for CubicMicrons {
+    type Output = Self;
+    fn neg(self) -> Self {
+        CubicMicrons(-self.0)
+    }
+}
+
+impl Sum for CubicMicrons {
+    fn sum<I: Iterator<Item = CubicMicrons>>(iter: I) -> Self {
+        iter.fold(CubicMicrons::ZERO, |acc, v| acc + v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squared_cubed() {
+        assert_eq!(Microns(-3).squared(), SquareMicrons(9));
+        assert_eq!(Microns(-3).cubed(), CubicMicrons(-27));
+        assert_eq!(Microns::MIN.cubed(), CubicMicrons(-(1 << 93)));
+        assert_eq!(Microns(10_000).cubed().as_ml(), 1.0);
+        assert_eq!(Microns(2000).squared().as_mm2(), 4.0);
+    }
+
+    #[test]
+    fn test_extrusion_volume() {
+        // 0.4mm x 0.2mm bead over 50mm
+        let bead = Microns(400) * Microns(200);
+        let segments = [Microns(20_000), Microns(30_000)];
+        let volume: CubicMicrons = segments.iter().map(|&len| bead * len).sum();
+        assert_eq!(volume.as_mm3(), 4.0);
+        assert_eq!(f64::from(volume), 4.0);
+    }
+}
@@ -0,0 +1,145 @@
+//! JavaScript bindings, exported as a `Microns` class.
+//!
+//! Every method goes through the same code as the Rust API, so a browser
+//! viewer truncates, parses and prints lengths exactly like the backend.
+//! Failures, such as overflow or an unknown unit, throw an `Error`.
+use crate::{exact, Microns, RoundingMode, Unit};
+use alloc::string::{String, ToString};
+use wasm_bindgen::prelude::*;
+
+/// `Microns` as seen from JavaScript.
+#[wasm_bindgen(js_name = Microns)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WasmMicrons(Microns);
+
+#[wasm_bindgen(js_class = Microns)]
+impl WasmMicrons {
+    /// A whole number of micrometers.
+    #[wasm_bindgen(constructor)]
+    pub fn new(um: i32) -> WasmMicrons {
+        WasmMicrons(Microns(um))
+    }
+
+    /// Millimeters, truncated toward zero.
+    #[wasm_bindgen(js_name = fromMm)]
+    pub fn from_mm(mm: f64) -> Result<WasmMicrons, JsError> {
+        Ok(WasmMicrons(Microns::try_from(mm)?))
+    }
+
+    /// Any string `FromStr` accepts, such as `"1.5"`, `"1.5mm"` or `"250um"`.
+    pub fn parse(s: &str) -> Result<WasmMicrons, JsError> {
+        Ok(WasmMicrons(s.parse()?))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn um(&self) -> i32 {
+        self.0 .0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn mm(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    pub fn add(&self, other: &WasmMicrons) -> Result<WasmMicrons, JsError> {
+        self.0
+            .checked_add(other.0)
+            .map(WasmMicrons)
+            .ok_or_else(|| JsError::new("attempt to add with overflow"))
+    }
+
+    pub fn sub(&self, other: &WasmMicrons) -> Result<WasmMicrons, JsError> {
+        self.0
+            .checked_sub(other.0)
+            .map(WasmMicrons)
+            .ok_or_else(|| JsError::new("attempt to subtract with overflow"))
+    }
+
+    /// Like `Microns * f32`: exact, then truncated toward zero.
+    pub fn mul(&self, factor: f32) -> Result<WasmMicrons, JsError> {
+        let um = exact::mul(self.0 .0 as i128, factor, RoundingMode::Trunc)?;
+        Ok(WasmMicrons(exact::narrow(um)?))
+    }
+
+    /// Like `Microns / f32`: exact, then truncated toward zero.
+    pub fn div(&self, divisor: f32) -> Result<WasmMicrons, JsError> {
+        let um = exact::div(self.0 .0 as i128, divisor, RoundingMode::Trunc)?;
+        Ok(WasmMicrons(exact::narrow(um)?))
+    }
+
+    /// The magnitude; `MIN` saturates to `MAX`.
+    pub fn abs(&self) -> WasmMicrons {
+        WasmMicrons(self.0.abs_diff(Microns::ZERO))
+    }
+
+    pub fn equals(&self, other: &WasmMicrons) -> bool {
+        self == other
+    }
+
+    /// -1, 0 or 1, for `Array.prototype.sort`.
+    pub fn compare(&self, other: &WasmMicrons) -> i32 {
+        self.cmp(other) as i32
+    }
+
+    /// Millimeters with three decimals, e.g. `"-12.345"`.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// `unit` is `"mm"`, `"um"` or `"in"`; `decimals` defaults to the
+    /// unit's usual precision. Rounds half away from zero.
+    pub fn format(&self, unit: &str, decimals: Option<u32>) -> Result<String, JsError> {
+        let unit = match unit {
+            "mm" => Unit::Mm,
+            "um" | "\u{b5}m" => Unit::Um,
+            "in" => Unit::Inch,
+            _ => return Err(JsError::new("unit must be mm, um or in")),
+        };
+        let mut format = self.0.format().unit(unit);
+        if let Some(decimals) = decimals {
+            if decimals > 18 {
+                return Err(JsError::new("at most 18 decimals are supported"));
+            }
+            format = format.decimals(decimals);
+        }
+        Ok(format.to_string())
+    }
+}
+
+impl From<Microns> for WasmMicrons {
+    fn from(m: Microns) -> Self {
+        WasmMicrons(m)
+    }
+}
+
+impl From<WasmMicrons> for Microns {
+    fn from(m: WasmMicrons) -> Self {
+        m.0
+    }
+}
+
+// `JsError` can only be built on a wasm target, so only the success paths
+// run natively.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_rust_api() {
+        let a = WasmMicrons::from_mm(1.2345).unwrap();
+        assert_eq!(a.um(), 1234);
+        let b = WasmMicrons::parse("0.010in").unwrap();
+        assert_eq!(Microns::from(b), Microns(254));
+        assert_eq!(a.add(&b).unwrap().um(), 1488);
+        assert_eq!(b.sub(&a).unwrap().abs(), WasmMicrons::new(980));
+        assert_eq!(a.mul(0.5).unwrap().um(), 617);
+        assert_eq!(a.div(3.0).unwrap().um(), 411);
+        assert_eq!(a.compare(&b), 1);
+        assert!(a.equals(&WasmMicrons::from(Microns(1234))));
+        assert_eq!(WasmMicrons::new(-1500).to_js_string(), "-1.500");
+        assert_eq!(a.format("in", None).unwrap(), "0.0486");
+        assert_eq!(a.format("um", Some(1)).unwrap(), "1234.0");
+        assert_eq!(a.mm(), 1.234);
+    }
+}